@@ -0,0 +1,101 @@
+//! Compare insertion throughput of the `BTreeMap`-backed
+//! `Bbow` and the `HashMap`-backed `HashedBbow` on a
+//! generated multi-megabyte corpus, and of the Unicode and
+//! ASCII-only word rules.
+//!
+//! Run with `cargo bench`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use bbow::{Bbow, BbowConfig, HashedBbow};
+
+/// Generate about `bytes` bytes of text drawn from a
+/// vocabulary of `vocabulary` made-up words, with a skewed
+/// frequency distribution.
+fn corpus(bytes: usize, vocabulary: usize) -> String {
+    const SYLLABLES: [&str; 16] = [
+        "ka", "lo", "mi", "ne", "ru", "sa", "to", "vi", "ba", "de", "fo", "gu", "hi", "ja", "pe",
+        "wo",
+    ];
+    let words: Vec<String> = (0..vocabulary)
+        .map(|mut n| {
+            let mut word = String::new();
+            loop {
+                word.push_str(SYLLABLES[n % SYLLABLES.len()]);
+                n /= SYLLABLES.len();
+                if n == 0 {
+                    break word;
+                }
+            }
+        })
+        .collect();
+
+    // A small linear congruential generator keeps the
+    // corpus deterministic without extra dependencies.
+    let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+    let mut text = String::with_capacity(bytes + 32);
+    while text.len() < bytes {
+        state = state
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1);
+        let r = (state >> 33) as usize;
+        // Squaring a uniform value skews it toward 0,
+        // favoring the first words of the vocabulary.
+        let index = r % vocabulary * (r % vocabulary) / vocabulary;
+        text.push_str(&words[index]);
+        text.push_str(if r.is_multiple_of(13) { ". " } else { " " });
+    }
+    text
+}
+
+fn time<F: FnMut()>(mut f: F) -> Duration {
+    const RUNS: u32 = 5;
+    let start = Instant::now();
+    for _ in 0..RUNS {
+        f();
+    }
+    start.elapsed() / RUNS
+}
+
+fn main() {
+    let text = corpus(8 << 20, 50_000);
+    let megabytes = text.len() as f64 / (1 << 20) as f64;
+
+    let btree = time(|| {
+        let bbow = Bbow::new().extend_from_text(&text);
+        black_box(bbow.len());
+    });
+    let hashed = time(|| {
+        let bbow = HashedBbow::new().extend_from_text(&text).into_bbow();
+        black_box(bbow.len());
+    });
+
+    let ascii_config = BbowConfig {
+        ascii_only: true,
+        ..BbowConfig::default()
+    };
+    let ascii = time(|| {
+        let bbow = Bbow::with_config(ascii_config).extend_from_text(&text);
+        black_box(bbow.len());
+    });
+    let hashed_ascii = time(|| {
+        let bbow = HashedBbow::with_config(ascii_config)
+            .extend_from_text(&text)
+            .into_bbow();
+        black_box(bbow.len());
+    });
+
+    for (name, elapsed) in [
+        ("BTreeMap", btree),
+        ("HashMap", hashed),
+        ("BTreeMap, ASCII", ascii),
+        ("HashMap, ASCII", hashed_ascii),
+    ] {
+        println!(
+            "{name:>15}: {:>8.2?} ({:.1} MB/s)",
+            elapsed,
+            megabytes / elapsed.as_secs_f64()
+        );
+    }
+}
@@ -0,0 +1,134 @@
+//! A compact binary encoding of [`Bbow`], enabled by the
+//! `bincode` feature.
+//!
+//! A BBOW is encoded with [`bincode`]'s standard
+//! configuration as its [`BbowConfig`], written as a short
+//! string of option names, followed by the same map from
+//! each word to its count used by the `serde` feature: a
+//! length, then each word and count in sorted word order,
+//! with the lengths and counts in a variable-length integer
+//! encoding.
+//!
+//! The encoding is not guaranteed to be stable across
+//! versions of this crate: bytes written by one version may
+//! not be readable by another.
+
+use std::collections::BTreeMap;
+
+use bincode::error::DecodeError;
+
+use crate::{Bbow, BbowConfig};
+
+impl Bbow<'_> {
+    /// Encode the configuration of this BBOW, its words and
+    /// their counts in a compact binary format, which can be
+    /// decoded with [`from_bytes`](Bbow::from_bytes).
+    /// Requires the `bincode` feature.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::new().extend_from_text("the cat and the hat");
+    /// let bytes = bbow.to_bytes();
+    /// assert_eq!(bbow, Bbow::from_bytes(&bytes).unwrap());
+    /// ```
+    pub fn to_bytes(&self) -> Vec<u8> {
+        bincode::serde::encode_to_vec((self.1.describe(), self), bincode::config::standard())
+            .expect("encoding words and counts into a vector cannot fail")
+    }
+}
+
+impl Bbow<'static> {
+    /// Decode a BBOW encoded by [`to_bytes`](Bbow::to_bytes).
+    /// The decoded BBOW owns its words, and has the same
+    /// words, counts and [`BbowConfig`] as the encoded one.
+    /// Requires the `bincode` feature.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if `bytes` is not a valid
+    /// encoding, holds a configuration that needs a feature
+    /// that is not enabled, holds a word not in the form
+    /// stored under that configuration or a count of zero,
+    /// or has bytes left over after the encoded BBOW.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        let ((config, words), read): ((String, BTreeMap<String, usize>), usize) =
+            bincode::serde::decode_from_slice(bytes, bincode::config::standard())?;
+        if read != bytes.len() {
+            return Err(DecodeError::Other("trailing bytes after encoded BBOW"));
+        }
+        let config = BbowConfig::parse(&config)
+            .ok_or_else(|| DecodeError::OtherString(format!("invalid configuration {config:?}")))?;
+        Bbow::from_stored_words(config, words).map_err(DecodeError::OtherString)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bytes_round_trip() {
+        let bytes = {
+            let text = String::from("The cat and the hat, café 日本語!");
+            Bbow::new().extend_from_text(&text).to_bytes()
+        };
+
+        let decoded: Bbow<'static> = Bbow::from_bytes(&bytes).unwrap();
+        assert_eq!(bytes, decoded.to_bytes());
+        assert_eq!(6, decoded.len());
+        assert_eq!(2, decoded.match_count("the"));
+        assert_eq!(1, decoded.match_count("日本語"));
+        assert_eq!(
+            Bbow::new(),
+            Bbow::from_bytes(&Bbow::new().to_bytes()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_bytes_round_trip_keeps_config() {
+        let config = BbowConfig {
+            case_sensitive: true,
+            min_len: 2,
+            ..BbowConfig::default()
+        };
+        let bbow = Bbow::with_config(config).extend_from_text("Apple apple APPLE apple a");
+        let decoded = Bbow::from_bytes(&bbow.to_bytes()).unwrap();
+        assert_eq!(bbow, decoded);
+        assert_eq!(config, *decoded.config());
+        assert_eq!(2, decoded.match_count("apple"));
+        assert_eq!(1, decoded.match_count("Apple"));
+
+        let config = BbowConfig {
+            keep_contractions: true,
+            keep_hyphens: true,
+            allow_digits: true,
+            ascii_only: true,
+            ..BbowConfig::default()
+        };
+        let bbow = Bbow::with_config(config).extend_from_text("Don't mother-in-law covid19 don't");
+        let decoded = Bbow::from_bytes(&bbow.to_bytes()).unwrap();
+        assert_eq!(bbow, decoded);
+        assert_eq!(config, *decoded.config());
+        assert_eq!(2, decoded.match_count("don't"));
+    }
+
+    #[test]
+    fn test_from_bytes_errors() {
+        let bytes = Bbow::new().extend_from_text("the cat").to_bytes();
+        assert!(Bbow::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+        let mut extra = bytes.clone();
+        extra.push(0);
+        assert!(Bbow::from_bytes(&extra).is_err());
+
+        let encode = |config: &str, word: &str, count: usize| {
+            let words = BTreeMap::from([(word, count)]);
+            bincode::serde::encode_to_vec((config, words), bincode::config::standard()).unwrap()
+        };
+        assert!(Bbow::from_bytes(&encode("min_len=0", "cat", 1)).is_ok());
+        assert!(Bbow::from_bytes(&encode("shouting min_len=0", "cat", 1)).is_err());
+        assert!(Bbow::from_bytes(&encode("min_len=0", "Cat", 1)).is_err());
+        assert!(Bbow::from_bytes(&encode("min_len=0", "cat", 0)).is_err());
+    }
+}
@@ -0,0 +1,183 @@
+//! A collection of documents for TF-IDF weighting.
+//!
+//! The count of a word in a single [`Bbow`] says how
+//! important the word is to that document, but not whether
+//! it is common to every document. A [`Corpus`] holds
+//! several documents so that words can be weighted by their
+//! term frequency times their inverse document frequency.
+
+use crate::{normalize, tokens, Bbow};
+
+/// A collection of documents, each a [`Bbow`], identified
+/// by the order they were added in, starting from 0.
+///
+/// For a word `w` in document `d` of a corpus of `N`
+/// documents, of which `df(w)` contain `w`:
+///
+/// * The term frequency `tf(w, d)` is the count of `w` in
+///   `d` divided by the overall number of words in `d`, as
+///   given by [`Bbow::probability`].
+/// * The inverse document frequency is
+///   `idf(w) = ln(N / df(w))`, using the natural logarithm.
+///   A word in no document has an `idf` of 0.0.
+/// * `tf_idf(w, d) = tf(w, d) * idf(w)`.
+///
+/// A word in every document thus has a TF-IDF of 0.0.
+/// Words are looked up as keywords, following the same
+/// rules as for [`Bbow::match_count`].
+///
+/// # Examples
+///
+/// ```
+/// # use bbow::{Bbow, Corpus};
+/// let mut corpus = Corpus::new();
+/// corpus.add_document(Bbow::new().extend_from_text("the cat sat"));
+/// corpus.add_document(Bbow::new().extend_from_text("the dog ran"));
+/// assert_eq!(0.0, corpus.tf_idf(0, "the"));
+/// assert!(corpus.tf_idf(0, "cat") > 0.0);
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct Corpus<'a>(Vec<Bbow<'a>>);
+
+impl<'a> Corpus<'a> {
+    /// Make a new empty corpus.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `document` to the end of this corpus. Its index
+    /// is the number of documents added before it.
+    pub fn add_document(&mut self, document: Bbow<'a>) {
+        self.0.push(document);
+    }
+
+    /// The document at `index`, or `None` if there is no
+    /// such document.
+    pub fn document(&self, index: usize) -> Option<&Bbow<'a>> {
+        self.0.get(index)
+    }
+
+    /// Count the documents in this corpus.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Is this corpus empty?
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Compute the inverse document frequency of `word`:
+    /// `ln(N / df)`, where `N` is the number of documents
+    /// and `df` the number of them containing `word`. If no
+    /// document contains `word` the result is 0.0.
+    pub fn idf(&self, word: &str) -> f64 {
+        let df = self.0.iter().filter(|doc| doc.contains(word)).count();
+        if df == 0 {
+            return 0.0;
+        }
+        (self.len() as f64 / df as f64).ln()
+    }
+
+    /// Compute the TF-IDF weight of `word` in the document
+    /// at `doc_index`: its relative frequency in that
+    /// document times its [`idf`](Self::idf). A missing
+    /// document contains no words, so gives 0.0.
+    pub fn tf_idf(&self, doc_index: usize, word: &str) -> f64 {
+        match self.document(doc_index) {
+            Some(doc) if doc.contains(word) => doc.probability(word) * self.idf(word),
+            _ => 0.0,
+        }
+    }
+
+    /// Rank the documents of this corpus by their relevance
+    /// to `query`, giving `(doc_index, score)` pairs sorted
+    /// by descending score, and by index among equal
+    /// scores. The query is split into words as in
+    /// [`Bbow::extend_from_text`], following the rules of
+    /// each document in turn, and a document's score is the
+    /// sum of the [`tf_idf`](Self::tf_idf) of each query
+    /// word in it: a word repeated in the query counts each
+    /// time. Documents scoring 0.0 are left out.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::{Bbow, Corpus};
+    /// let mut corpus = Corpus::new();
+    /// corpus.add_document(Bbow::new().extend_from_text("the cat sat"));
+    /// corpus.add_document(Bbow::new().extend_from_text("the dog ran"));
+    /// let results = corpus.search("Dog!");
+    /// assert_eq!(1, results.len());
+    /// assert_eq!(1, results[0].0);
+    /// ```
+    pub fn search(&self, query: &str) -> Vec<(usize, f64)> {
+        let mut results: Vec<(usize, f64)> = self
+            .0
+            .iter()
+            .enumerate()
+            .filter_map(|(index, doc)| {
+                let score: f64 = tokens(query)
+                    .filter_map(|token| normalize(token, doc.config()))
+                    .map(|word| self.tf_idf(index, &word))
+                    .sum();
+                (score > 0.0).then_some((index, score))
+            })
+            .collect();
+        // The sort is stable, so equal scores stay in index
+        // order.
+        results.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+        results
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tf_idf() {
+        let mut corpus = Corpus::new();
+        corpus.add_document(Bbow::new().extend_from_text("the cat sat on the cat"));
+        corpus.add_document(Bbow::new().extend_from_text("the dog"));
+        assert_eq!(2, corpus.len());
+
+        // "cat" is 2 of the 6 words of document 0, and in 1
+        // of the 2 documents: (2 / 6) * ln(2 / 1).
+        let expected = 2.0 / 6.0 * 2.0f64.ln();
+        assert!((corpus.tf_idf(0, "cat") - expected).abs() < 1e-12);
+        assert!((corpus.idf("cat") - 2.0f64.ln()).abs() < 1e-12);
+        assert_eq!(0.0, corpus.tf_idf(1, "cat"));
+
+        // "the" is in every document.
+        assert_eq!(0.0, corpus.idf("the"));
+        assert_eq!(0.0, corpus.tf_idf(0, "the"));
+
+        assert_eq!(0.0, corpus.idf("bird"));
+        assert_eq!(0.0, corpus.tf_idf(0, "Cat"));
+        assert_eq!(0.0, corpus.tf_idf(2, "cat"));
+        assert_eq!(0.0, Corpus::new().idf("cat"));
+    }
+
+    #[test]
+    fn test_search() {
+        let mut corpus = Corpus::new();
+        corpus.add_document(Bbow::new().extend_from_text("the cat sat on the mat"));
+        corpus.add_document(Bbow::new().extend_from_text("a rust compiler compiles rust code"));
+        corpus.add_document(Bbow::new().extend_from_text("the dog and the cat"));
+
+        let results = corpus.search("Rust compiler?");
+        assert_eq!(1, results.len());
+        assert_eq!(1, results[0].0);
+        let expected = corpus.tf_idf(1, "rust") + corpus.tf_idf(1, "compiler");
+        assert!((results[0].1 - expected).abs() < 1e-12);
+
+        // "cat" is in two documents, and most frequent in the
+        // shorter one.
+        let indexes: Vec<usize> = corpus.search("cat").into_iter().map(|(i, _)| i).collect();
+        assert_eq!(vec![2, 0], indexes);
+
+        assert!(corpus.search("bird").is_empty());
+        assert!(corpus.search("").is_empty());
+    }
+}
@@ -0,0 +1,184 @@
+//! Saving and loading [`Bbow`]s as gzip-compressed files,
+//! enabled by the `gzip` feature.
+//!
+//! A BBOW is saved as a line describing its
+//! [`BbowConfig`], such as `# config: case_sensitive
+//! min_len=2`, followed by the CSV text given by
+//! [`Bbow::to_csv`], compressed with [`flate2`].
+
+use std::borrow::Cow;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+
+use crate::{Bbow, BbowConfig};
+
+/// The start of the line describing the configuration.
+const CONFIG_PREFIX: &str = "# config: ";
+
+impl Bbow<'_> {
+    /// Save the words of this BBOW and their counts to the
+    /// file at `path`, as gzip-compressed
+    /// [CSV](Bbow::to_csv) preceded by a line describing its
+    /// configuration, replacing any existing file. Requires
+    /// the `gzip` feature.
+    ///
+    /// # Errors
+    ///
+    /// Any error creating or writing the file is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::new().extend_from_text("the cat and the hat");
+    /// bbow.save_gz("counts.csv.gz")?;
+    /// assert_eq!(bbow, Bbow::load_gz("counts.csv.gz")?);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn save_gz<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let file = BufWriter::new(File::create(path)?);
+        let mut encoder = GzEncoder::new(file, Compression::default());
+        writeln!(encoder, "{CONFIG_PREFIX}{}", self.1.describe())?;
+        encoder.write_all(self.to_csv().as_bytes())?;
+        encoder.finish()?.flush()
+    }
+}
+
+impl Bbow<'static> {
+    /// Load a BBOW saved by [`save_gz`](Bbow::save_gz) from
+    /// the file at `path`. The loaded BBOW owns its words,
+    /// and has the same words, counts and [`BbowConfig`] as
+    /// the saved one. Requires the `gzip` feature.
+    ///
+    /// # Errors
+    ///
+    /// Any error opening, reading or decompressing the file
+    /// is returned. A file that is not valid UTF-8, lacks
+    /// the configuration line or the `word,count` header,
+    /// has a configuration that needs a feature that is not
+    /// enabled, or has a row that is not a word in the form
+    /// stored under that configuration and a non-zero count
+    /// gives an error of kind [`io::ErrorKind::InvalidData`].
+    pub fn load_gz<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let reader = BufReader::new(GzDecoder::new(File::open(path)?));
+        let mut lines = reader.lines();
+        let line = lines.next().transpose()?.unwrap_or_default();
+        let config = line
+            .strip_prefix(CONFIG_PREFIX)
+            .and_then(BbowConfig::parse)
+            .ok_or_else(|| invalid_data(format!("invalid configuration line {line:?}")))?;
+        if lines.next().transpose()?.as_deref() != Some("word,count") {
+            return Err(invalid_data("missing word,count header".to_string()));
+        }
+        let mut words = Vec::new();
+        for line in lines {
+            let line = line?;
+            let (word, count) =
+                parse_row(&line).ok_or_else(|| invalid_data(format!("invalid row {line:?}")))?;
+            words.push((word.into_owned(), count));
+        }
+        Bbow::from_stored_words(config, words).map_err(invalid_data)
+    }
+}
+
+/// Split a `word,count` CSV row, unquoting the word if
+/// needed. Returns `None` unless the count is a non-zero
+/// number.
+fn parse_row(line: &str) -> Option<(Cow<'_, str>, usize)> {
+    let (word, count) = line.rsplit_once(',')?;
+    let count = count.parse().ok().filter(|&count| count > 0)?;
+    let word = match word.strip_prefix('"').and_then(|w| w.strip_suffix('"')) {
+        Some(quoted) => Cow::Owned(quoted.replace("\"\"", "\"")),
+        None => Cow::Borrowed(word),
+    };
+    Some((word, count))
+}
+
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("bbow-{}-{}", std::process::id(), name))
+    }
+
+    #[test]
+    fn test_gz_round_trip() {
+        let path = temp_path("round_trip.csv.gz");
+        let bbow = Bbow::new().extend_from_text("The cat and the hat, café 日本語!");
+        bbow.save_gz(&path).unwrap();
+        let loaded = Bbow::load_gz(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(bbow, loaded);
+        for (word, count) in &bbow {
+            assert_eq!(count, loaded.match_count(word));
+        }
+
+        let config = BbowConfig {
+            case_sensitive: true,
+            keep_contractions: true,
+            keep_hyphens: true,
+            allow_digits: true,
+            min_len: 2,
+            ..BbowConfig::default()
+        };
+        let path = temp_path("config.csv.gz");
+        let bbow = Bbow::with_config(config)
+            .extend_from_text("Apple apple APPLE apple a Don't mother-in-law covid19");
+        bbow.save_gz(&path).unwrap();
+        let loaded = Bbow::load_gz(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(bbow, loaded);
+        assert_eq!(config, *loaded.config());
+        assert_eq!(2, loaded.match_count("apple"));
+        assert_eq!(1, loaded.match_count("Don't"));
+
+        let path = temp_path("empty.csv.gz");
+        Bbow::new().save_gz(&path).unwrap();
+        let loaded = Bbow::load_gz(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert!(loaded.is_empty());
+    }
+
+    #[test]
+    fn test_load_gz_errors() {
+        let missing = Bbow::load_gz(temp_path("missing.csv.gz")).unwrap_err();
+        assert_eq!(io::ErrorKind::NotFound, missing.kind());
+
+        // Not gzip-compressed
+        let path = temp_path("plain.csv");
+        std::fs::write(&path, "# config: min_len=0\nword,count\ncat,1\n").unwrap();
+        let result = Bbow::load_gz(&path);
+        std::fs::remove_file(&path).unwrap();
+        assert!(result.is_err());
+
+        for csv in [
+            "word,count\ncat,1\n",
+            "# config: shouting min_len=0\nword,count\ncat,1\n",
+            "# config: min_len=0\ncat,1\n",
+            "# config: min_len=0\nword,count\ncat\n",
+            "# config: min_len=0\nword,count\n42,1\n",
+            "# config: min_len=0\nword,count\nCat,1\n",
+            "# config: min_len=0\nword,count\ncat,0\n",
+            "# config: min_len=0\nword,count\ncat,1\ncat,2\n",
+        ] {
+            let path = temp_path("invalid.csv.gz");
+            let mut encoder = GzEncoder::new(File::create(&path).unwrap(), Compression::default());
+            encoder.write_all(csv.as_bytes()).unwrap();
+            encoder.finish().unwrap();
+            let error = Bbow::load_gz(&path).unwrap_err();
+            std::fs::remove_file(&path).unwrap();
+            assert_eq!(io::ErrorKind::InvalidData, error.kind());
+        }
+    }
+}
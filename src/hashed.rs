@@ -0,0 +1,266 @@
+//! A hash-map-backed builder for [`Bbow`].
+//!
+//! Inserting into a [`BTreeMap`](std::collections::BTreeMap)
+//! costs `O(log n)` comparisons per word. When parsing a
+//! large amount of text in one go, it can be faster to
+//! count words in a [`HashMap`] and sort them once, when
+//! converting to a [`Bbow`] at the end.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+use crate::{compose, is_keyword, most_common, normalize, tokens, Bbow, BbowConfig};
+
+/// Word counts held in a [`HashMap`], for fast insertion.
+/// The words follow the same rules as for [`Bbow`].
+///
+/// This is a separate type rather than a backend of
+/// [`Bbow`], so it offers only part of the [`Bbow`] API:
+/// building, the basic lookups ([`get`](Self::get),
+/// [`match_count`](Self::match_count),
+/// [`contains`](Self::contains)) and the sorted views
+/// [`words`](Self::words) and
+/// [`most_common`](Self::most_common), which sort on each
+/// call. Convert to a [`Bbow`] with
+/// [`into_bbow`](Self::into_bbow) (or [`From`]) once
+/// parsing is done for everything else.
+///
+/// # Examples
+///
+/// ```
+/// # use bbow::{Bbow, HashedBbow};
+/// let mut hashed = HashedBbow::new();
+/// hashed.add_text("Hello world.");
+/// hashed.add_text("Hello again!");
+/// let bbow: Bbow = hashed.into_bbow();
+/// assert_eq!(2, bbow.match_count("hello"));
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct HashedBbow<'a>(HashMap<Cow<'a, str>, usize>, BbowConfig);
+
+impl<'a> HashedBbow<'a> {
+    /// Make a new empty target words list.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Make a new empty target words list with room for at
+    /// least `capacity` unique words before reallocating.
+    pub fn with_capacity(capacity: usize) -> Self {
+        HashedBbow(HashMap::with_capacity(capacity), BbowConfig::default())
+    }
+
+    /// Make a new empty target words list that turns text
+    /// into words according to `config`.
+    pub fn with_config(config: BbowConfig) -> Self {
+        HashedBbow(HashMap::new(), config)
+    }
+
+    /// The options controlling how this turns text into
+    /// words.
+    pub fn config(&self) -> &BbowConfig {
+        &self.1
+    }
+
+    /// Parse the `target` text and add the sequence of
+    /// valid words contained in it, as in
+    /// [`Bbow::extend_from_text`].
+    pub fn extend_from_text(mut self, target: &'a str) -> Self {
+        self.add_text(target);
+        self
+    }
+
+    /// Parse the `target` text and add the sequence of
+    /// valid words contained in it in place, as in
+    /// [`Bbow::add_text`].
+    pub fn add_text(&mut self, target: &'a str) {
+        for token in tokens(target) {
+            if let Some(word) = normalize(token, &self.1) {
+                *self.0.entry(word).or_insert(0) += 1;
+            }
+        }
+    }
+
+    /// Count the overall number of words added: multiple
+    /// occurrences are considered separate.
+    pub fn count(&self) -> usize {
+        self.0.values().sum()
+    }
+
+    /// Count the number of unique words added.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Is this empty?
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Report the number of occurrences of the given
+    /// `keyword`, or `None` if it has not been added, as in
+    /// [`Bbow::get`].
+    pub fn get(&self, keyword: &str) -> Option<usize> {
+        let keyword = compose(keyword, &self.1);
+        if !is_keyword(&keyword, &self.1) {
+            return None;
+        }
+        self.0.get(keyword.as_ref()).copied()
+    }
+
+    /// Report the number of occurrences of the given
+    /// `keyword`, as in [`Bbow::match_count`].
+    pub fn match_count(&self, keyword: &str) -> usize {
+        self.get(keyword).unwrap_or(0)
+    }
+
+    /// Report whether the given `keyword` has been added, as
+    /// in [`Bbow::contains`].
+    pub fn contains(&self, keyword: &str) -> bool {
+        self.get(keyword).is_some()
+    }
+
+    /// Iterate over the words added, in sorted order, as in
+    /// [`Bbow::words`]. The words are sorted on each call.
+    pub fn words(&self) -> impl Iterator<Item = &str> {
+        let mut words: Vec<&str> = self.0.keys().map(|word| word.as_ref()).collect();
+        words.sort_unstable();
+        words.into_iter()
+    }
+
+    /// Report up to `n` of the most frequent words added as
+    /// `(word, count)` pairs, as in [`Bbow::most_common`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::HashedBbow;
+    /// let hashed = HashedBbow::new()
+    ///     .extend_from_text("the cat and the hat and the bat");
+    /// assert_eq!(vec![("the", 3), ("and", 2)], hashed.most_common(2));
+    /// ```
+    pub fn most_common(&self, n: usize) -> Vec<(&str, usize)> {
+        most_common(
+            self.0.iter().map(|(word, &count)| (word.as_ref(), count)),
+            n,
+        )
+    }
+
+    /// Keep only the words for which the predicate `f`
+    /// returns `true`, as in [`Bbow::retain`].
+    pub fn retain<F: FnMut(&str, usize) -> bool>(&mut self, mut f: F) {
+        self.0.retain(|word, count| f(word, *count));
+    }
+
+    /// The number of unique words this can hold without
+    /// reallocating.
+    pub fn capacity(&self) -> usize {
+        self.0.capacity()
+    }
+
+    /// Release as much unused capacity as possible, for
+    /// instance after [`retain`](Self::retain) has removed
+    /// most of the words of a large parse.
+    pub fn shrink_to_fit(&mut self) {
+        self.0.shrink_to_fit();
+    }
+
+    /// Sort the words into a [`Bbow`] with the same
+    /// configuration.
+    pub fn into_bbow(self) -> Bbow<'a> {
+        Bbow(self.0.into_iter().collect(), self.1)
+    }
+}
+
+impl<'a> From<HashedBbow<'a>> for Bbow<'a> {
+    fn from(hashed: HashedBbow<'a>) -> Self {
+        hashed.into_bbow()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches_btree_backend() {
+        let text = "Lets iterate over this text. Lets iterate over THAT text, café 日本語!";
+        let expected = Bbow::new().extend_from_text(text).extend_from_text(text);
+        let hashed = HashedBbow::new()
+            .extend_from_text(text)
+            .extend_from_text(text);
+        assert_eq!(expected.len(), hashed.len());
+        assert_eq!(expected.count(), hashed.count());
+
+        let bbow = hashed.into_bbow();
+        assert_eq!(
+            (&expected).into_iter().collect::<Vec<_>>(),
+            (&bbow).into_iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_queries() {
+        let text = "The cat and the hat and THE bat, café!";
+        let bbow = Bbow::new().extend_from_text(text);
+        let hashed = HashedBbow::new().extend_from_text(text);
+
+        assert_eq!(
+            bbow.words().collect::<Vec<_>>(),
+            hashed.words().collect::<Vec<_>>()
+        );
+        for n in [0, 1, 2, 5, 10] {
+            assert_eq!(bbow.most_common(n), hashed.most_common(n));
+        }
+        for keyword in ["the", "The", "café", "cafe\u{301}", "dog", "the!", ""] {
+            assert_eq!(bbow.get(keyword), hashed.get(keyword));
+            assert_eq!(bbow.match_count(keyword), hashed.match_count(keyword));
+            assert_eq!(bbow.contains(keyword), hashed.contains(keyword));
+        }
+        assert_eq!(bbow.config(), hashed.config());
+    }
+
+    #[test]
+    fn test_keeps_config() {
+        let config = BbowConfig {
+            case_sensitive: true,
+            min_len: 2,
+            ..BbowConfig::default()
+        };
+        let bbow: Bbow = HashedBbow::with_config(config)
+            .extend_from_text("A Test test")
+            .into();
+        assert_eq!(config, *bbow.config());
+        assert_eq!(2, bbow.len());
+        assert_eq!(1, bbow.match_count("Test"));
+    }
+
+    #[test]
+    fn test_shrink_to_fit() {
+        // Distinct words "a", "b", ..., "ba", "bb", ...
+        let word = |mut n: usize| {
+            let mut word = String::new();
+            loop {
+                word.insert(0, char::from(b'a' + (n % 26) as u8));
+                n /= 26;
+                if n == 0 {
+                    return word;
+                }
+            }
+        };
+        let text: String = (0..5000).map(|n| word(n) + " ").collect();
+
+        let mut hashed = HashedBbow::new();
+        for _ in 0..3 {
+            hashed.add_text(&text);
+            let before = hashed.capacity();
+            assert!(before >= 5000);
+            hashed.retain(|word, _| word.len() == 1);
+            assert_eq!(26, hashed.len());
+            hashed.shrink_to_fit();
+            assert!(hashed.capacity() >= hashed.len());
+            assert!(hashed.capacity() < before / 10);
+        }
+        assert_eq!(3 * 26, hashed.count());
+    }
+}
@@ -0,0 +1,232 @@
+//! Bags of words sharing the text of their words.
+//!
+//! A [`Bbow`] that outlives its text, such as one built with
+//! [`Bbow::extend_from_reader`], owns a separate copy of
+//! every word, so many bags over similar text hold many
+//! copies of the same words. The bags made by an
+//! [`Interner`] instead share a single reference-counted
+//! copy of each word.
+
+use std::collections::{BTreeMap, HashSet};
+use std::io::{self, BufRead};
+use std::sync::{Arc, Mutex, PoisonError};
+
+use crate::{compose, is_keyword, normalize, tokens, Bbow, BbowConfig};
+
+/// A pool of words shared by the [`InternedBbow`]s it
+/// makes, so that each word is stored once however many
+/// bags contain it.
+///
+/// An `Interner` is a cheap handle to its pool: clones
+/// share the same pool. The pool is guarded by a
+/// [`Mutex`], so an `Interner` and its bags are [`Send`]
+/// and [`Sync`], and bags on different threads may add
+/// text at the same time. A bag only locks the pool the
+/// first time it sees each word. Words stay in the pool as
+/// long as the `Interner` or any of its clones lives, even
+/// once no bag contains them.
+///
+/// # Examples
+///
+/// ```
+/// # use bbow::Interner;
+/// let interner = Interner::new();
+/// let mut a = interner.bag();
+/// let mut b = interner.bag();
+/// a.add_text("the cat");
+/// b.add_text("the hat");
+/// assert_eq!(1, b.match_count("the"));
+/// assert_eq!(3, interner.len());
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct Interner(Arc<Mutex<HashSet<Arc<str>>>>);
+
+impl Interner {
+    /// Make a new interner with an empty pool.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Make a new empty bag drawing its words from this
+    /// interner's pool.
+    pub fn bag(&self) -> InternedBbow {
+        self.bag_with_config(BbowConfig::default())
+    }
+
+    /// Make a new empty bag drawing its words from this
+    /// interner's pool, that turns text into words according
+    /// to `config`.
+    pub fn bag_with_config(&self, config: BbowConfig) -> InternedBbow {
+        InternedBbow(BTreeMap::new(), config, self.clone())
+    }
+
+    /// Report the number of unique words in the pool.
+    pub fn len(&self) -> usize {
+        self.pool().len()
+    }
+
+    /// Is the pool empty?
+    pub fn is_empty(&self) -> bool {
+        self.pool().is_empty()
+    }
+
+    fn pool(&self) -> std::sync::MutexGuard<'_, HashSet<Arc<str>>> {
+        // Inserting into the set cannot be left half done,
+        // so a poisoned pool is still usable.
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Get the pooled copy of `word`, adding it if needed.
+    fn intern(&self, word: &str) -> Arc<str> {
+        let mut pool = self.pool();
+        match pool.get(word) {
+            Some(interned) => Arc::clone(interned),
+            None => {
+                let interned: Arc<str> = Arc::from(word);
+                pool.insert(Arc::clone(&interned));
+                interned
+            }
+        }
+    }
+}
+
+/// A bag of words whose words are shared with the other
+/// bags of the [`Interner`] that made it. The words follow
+/// the same rules as for [`Bbow`]; convert to a [`Bbow`]
+/// with [`to_bbow`](Self::to_bbow) for the full set of
+/// queries.
+#[derive(Debug, Clone)]
+pub struct InternedBbow(BTreeMap<Arc<str>, usize>, BbowConfig, Interner);
+
+impl InternedBbow {
+    /// Parse the `target` text and add the sequence of
+    /// valid words contained in it in place, as in
+    /// [`Bbow::add_text`].
+    pub fn add_text(&mut self, target: &str) {
+        for token in tokens(target) {
+            if let Some(word) = normalize(token, &self.1) {
+                if let Some(count) = self.0.get_mut(word.as_ref()) {
+                    *count += 1;
+                } else {
+                    self.0.insert(self.2.intern(&word), 1);
+                }
+            }
+        }
+    }
+
+    /// Add words read line by line from `reader`, as in
+    /// [`Bbow::extend_from_reader`].
+    ///
+    /// # Errors
+    ///
+    /// Any error reading from `reader` is returned, as for
+    /// [`Bbow::extend_from_reader`].
+    pub fn extend_from_reader<R: BufRead>(&mut self, mut reader: R) -> io::Result<()> {
+        let mut line = String::new();
+        while reader.read_line(&mut line)? > 0 {
+            self.add_text(&line);
+            line.clear();
+        }
+        Ok(())
+    }
+
+    /// Report the number of occurrences of the given
+    /// `keyword`, as in [`Bbow::match_count`].
+    pub fn match_count(&self, keyword: &str) -> usize {
+        let keyword = compose(keyword, &self.1);
+        if !is_keyword(&keyword, &self.1) {
+            return 0;
+        }
+        self.0.get(keyword.as_ref()).copied().unwrap_or(0)
+    }
+
+    /// Count the overall number of words in this bag:
+    /// multiple occurrences are considered separate.
+    pub fn count(&self) -> usize {
+        self.0.values().sum()
+    }
+
+    /// Count the number of unique words in this bag.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Is this bag empty?
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Iterate over the words of this bag with their counts,
+    /// in alphabetical order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, usize)> {
+        self.0.iter().map(|(word, &count)| (word.as_ref(), count))
+    }
+
+    /// Copy the words of this bag into a [`Bbow`] with the
+    /// same configuration.
+    pub fn to_bbow(&self) -> Bbow<'static> {
+        let mut bbow = Bbow::with_config(self.1);
+        for (word, &count) in &self.0 {
+            bbow.0.insert(word.to_string().into(), count);
+        }
+        bbow
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shares_words() {
+        let interner = Interner::new();
+        let mut a = interner.bag();
+        let mut b = interner.bag();
+        a.add_text("The cat and the hat.");
+        b.extend_from_reader("A CAT\nsat".as_bytes()).unwrap();
+        assert_eq!(2, a.match_count("the"));
+        assert_eq!(1, b.match_count("cat"));
+        assert_eq!(6, interner.len());
+
+        let (in_a, _) = a.0.get_key_value("cat").unwrap();
+        let (in_b, _) = b.0.get_key_value("cat").unwrap();
+        assert!(Arc::ptr_eq(in_a, in_b));
+
+        let other = Interner::new();
+        let mut c = other.bag();
+        c.add_text("cat");
+        let (in_c, _) = c.0.get_key_value("cat").unwrap();
+        assert!(!Arc::ptr_eq(in_a, in_c));
+    }
+
+    #[test]
+    fn test_matches_bbow() {
+        let text = "Lets iterate over this text. Lets iterate over THAT text, café 日本語!";
+        let config = BbowConfig {
+            case_sensitive: true,
+            ..BbowConfig::default()
+        };
+        let interner = Interner::new();
+        let mut bag = interner.bag_with_config(config);
+        bag.add_text(text);
+        let expected = Bbow::with_config(config).extend_from_text(text);
+        assert_eq!(expected, bag.to_bbow());
+        assert_eq!(expected.count(), bag.count());
+        assert_eq!(expected.len(), bag.len());
+        assert_eq!(
+            (&expected).into_iter().collect::<Vec<_>>(),
+            bag.iter().collect::<Vec<_>>()
+        );
+        assert_eq!(1, bag.match_count("THAT"));
+
+        // Bags may be filled on other threads.
+        let mut bag = interner.bag();
+        std::thread::spawn(move || {
+            bag.add_text("thread");
+            bag
+        })
+        .join()
+        .unwrap();
+        assert_eq!(expected.len() + 1, interner.len());
+    }
+}
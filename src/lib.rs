@@ -14,6 +14,17 @@
 //! punctuation: leading and trailing punctuation are
 //! removed.
 //!
+//! Whitespace is any code point with the Unicode
+//! `White_Space` property, which includes the non-breaking
+//! spaces often found in text copied from the web, plus the
+//! zero-width space `U+200B`. In full the separators are
+//! `U+0009`–`U+000D`, `U+0020`, `U+0085`, `U+00A0`,
+//! `U+1680`, `U+2000`–`U+200B`, `U+2028`, `U+2029`,
+//! `U+202F`, `U+205F` and `U+3000`. The zero-width joiners
+//! `U+200C` and `U+200D`, which are part of words in some
+//! scripts, are not separators. A byte order mark at the
+//! start of a text is ignored.
+//!
 //! For example, the text
 //!
 //! ```text
@@ -23,24 +34,500 @@
 //! contains the sequence of words `"It"`, `"over"`,
 //! `"untïl"`, `"it"`, `"over"`.
 //!
+//! A [`BbowConfig`] can relax these rules, for example to
+//! keep contractions like `"ain't"` as words.
+//!
 //! Words in the bag containing uppercase letters will be
-//! represented by their lowercase equivalent.
+//! represented by their lowercase equivalent, unless the
+//! bag is created [case sensitive](BbowConfig::case_sensitive):
+//! then `"Apple"` and `"apple"` are kept as distinct
+//! words, and keywords must match the stored case exactly.
+//!
+//! Lowercasing never depends on the locale or platform: it
+//! uses Unicode's default case mappings, except that `'İ'`
+//! becomes a plain `'i'`. So `"İstanbul"`, `"ISTANBUL"` and
+//! `"istanbul"` are all the word `"istanbul"`, while the
+//! dotless `'ı'` is kept distinct from `'i'`. Use
+//! [`Bbow::match_count_normalized`] to look up a keyword
+//! with the same conversion applied.
+//!
+//! # Features
+//!
+//! * `bincode`: provide `Bbow::to_bytes` and
+//!   `Bbow::from_bytes`, encoding a bag in a compact binary
+//!   format with `bincode`. Enables `serde`.
+//! * `case-fold`: use full Unicode case folding rather than
+//!   plain lowercasing, so that words differing only in
+//!   case always match. Beyond lowercasing this maps `ß`
+//!   and `ẞ` to `ss`, final sigma `ς` to `σ`, long s `ſ` to
+//!   `s`, the Latin ligatures `ﬀ ﬁ ﬂ ﬃ ﬄ ﬅ ﬆ` to their
+//!   letters, and the Greek symbol variants `ϐ ϑ ϕ ϖ ϰ ϱ ϵ`
+//!   to `β θ φ π κ ρ ε`. For example `"Straße"` and
+//!   `"STRASSE"` are both stored as `"strasse"`.
+//! * `gzip`: provide `Bbow::save_gz` and `Bbow::load_gz`
+//!   to save a bag to a gzip-compressed CSV file and load it
+//!   back.
+//! * `intern`: provide `Interner`, which makes bags of
+//!   words that share a single copy of each word rather
+//!   than each owning their own.
+//! * `rand`: provide `Bbow::sample` to draw random words
+//!   in proportion to their counts.
+//! * `rayon`: provide `Bbow::par_extend_from_text` to parse
+//!   large texts on multiple threads.
+//! * `serde`: implement `Serialize` and `Deserialize` for
+//!   [`Bbow`], representing a bag as a map from word to
+//!   count.
+//! * `stopwords`: provide a builtin list of common English
+//!   stopwords in the `stopwords` module, and
+//!   `Bbow::remove_stopwords_en` to remove them.
+//! * `unicode-normalization`: convert text and keywords to
+//!   Unicode Normalization Form C before processing, so that
+//!   precomposed and decomposed spellings of the same word
+//!   (for example `"café"` written with `'é'` or with `'e'`
+//!   followed by a combining acute accent) are counted
+//!   together. Also provides the
+//!   `BbowConfig::strip_accents` option to count `"café"`
+//!   and `"cafe"` as the same word.
 
 use std::borrow::Cow;
-use std::collections::BTreeMap;
+use std::cmp::{Ordering, Reverse};
+use std::collections::{btree_map, BTreeMap, BinaryHeap};
+use std::fmt;
+use std::fs::File;
+use std::hash::{Hash, Hasher};
+use std::io::{self, BufRead, BufReader};
+use std::ops::{Add, AddAssign, BitAnd, BitOr, Mul};
+use std::ops::{Bound, Range};
+use std::path::Path;
+
+#[cfg(feature = "bincode")]
+mod binary;
+mod corpus;
+#[cfg(feature = "gzip")]
+mod gzip;
+mod hashed;
+#[cfg(feature = "intern")]
+mod intern;
+#[cfg(feature = "serde")]
+mod serde_impl;
+#[cfg(feature = "stopwords")]
+pub mod stopwords;
+mod top_k;
+
+pub use corpus::Corpus;
+pub use hashed::HashedBbow;
+#[cfg(feature = "intern")]
+pub use intern::{InternedBbow, Interner};
+pub use top_k::TopK;
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+#[cfg(feature = "unicode-normalization")]
+use unicode_normalization::{char::is_combining_mark, is_nfc, UnicodeNormalization};
 
 /// Each key in this struct's map is a word in some
 /// in-memory text document. The corresponding value is the
 /// count of occurrences.
+///
+/// The [`BbowConfig`] controls how text is split into
+/// words.
 #[derive(Debug, Default, Clone)]
-pub struct Bbow<'a>(BTreeMap<Cow<'a, str>, usize>);
+pub struct Bbow<'a>(BTreeMap<Cow<'a, str>, usize>, BbowConfig);
+
+/// Options controlling how a [`Bbow`] turns text into
+/// words. The default options give the rules described in
+/// the [crate documentation](crate).
+///
+/// # Examples
+///
+/// ```
+/// # use bbow::{Bbow, BbowConfig};
+/// let config = BbowConfig {
+///     case_sensitive: true,
+///     ..BbowConfig::default()
+/// };
+/// let bbow = Bbow::with_config(config).extend_from_text("Test test");
+/// assert_eq!(2, bbow.len());
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct BbowConfig {
+    /// Keep words in their original case rather than
+    /// converting them to lowercase, so that `"Test"` and
+    /// `"test"` are different words. Keywords are then
+    /// matched exactly.
+    pub case_sensitive: bool,
+    /// Skip words shorter than this many characters
+    /// (Unicode scalar values, not bytes).
+    pub min_len: usize,
+    /// Keep an apostrophe between two letters as part of a
+    /// word, so that contractions like `"don't"` are counted
+    /// rather than ignored. Both the ASCII apostrophe `'`
+    /// and the right single quotation mark `’` are accepted;
+    /// the latter is stored as `'`, so `"don’t"` and
+    /// `"don't"` are the same word. Leading and trailing
+    /// apostrophes are still trimmed.
+    pub keep_contractions: bool,
+    /// Keep a hyphen `-` between two letters as part of a
+    /// word, so that `"mother-in-law"` is a single word.
+    /// Leading and trailing hyphens are still trimmed. The
+    /// hyphens count towards [`min_len`](Self::min_len).
+    pub keep_hyphens: bool,
+    /// Treat numeric characters such as digits like
+    /// letters, so that `"covid19"` and `"3d"` are words.
+    /// Tokens made only of digits, like `"2024"`, are then
+    /// words too.
+    pub allow_digits: bool,
+    /// Remove accents and other combining marks from words,
+    /// so that `"café"` and `"cafe"` are the same word,
+    /// stored as `"cafe"`. Keywords have their accents
+    /// removed too. Text is decomposed to Unicode
+    /// Normalization Form D and every combining mark is
+    /// dropped, which suits Latin, Greek and Cyrillic text
+    /// but damages scripts whose marks are essential, such
+    /// as the vowel signs of Devanagari or Thai. Letters
+    /// with no decomposition, like `'ø'` or `'ł'`, are kept.
+    /// Requires the `unicode-normalization` feature.
+    #[cfg(feature = "unicode-normalization")]
+    pub strip_accents: bool,
+    /// Only treat ASCII letters (and ASCII digits, with
+    /// [`allow_digits`](Self::allow_digits)) as word
+    /// characters, which parses ASCII text faster. Other
+    /// characters are treated like punctuation: they are
+    /// trimmed from the ends of a token, and a token
+    /// containing one between letters is skipped. So
+    /// `"“quoted”"` is the word `"quoted"`, but `"café"` is
+    /// `"caf"` and `"naïve"` is skipped. Only the ASCII
+    /// apostrophe `'` is kept in contractions.
+    pub ascii_only: bool,
+}
+
+impl BbowConfig {
+    /// Is `c` a character that words are made of?
+    fn is_letter(&self, c: char) -> bool {
+        if self.ascii_only {
+            c.is_ascii_alphabetic() || (self.allow_digits && c.is_ascii_digit())
+        } else {
+            c.is_alphabetic() || (self.allow_digits && c.is_numeric())
+        }
+    }
+
+    /// Can `c` join two letters within a word?
+    fn is_joiner(&self, c: char) -> bool {
+        (self.keep_contractions && (c == '\'' || (c == '\u{2019}' && !self.ascii_only)))
+            || (self.keep_hyphens && c == '-')
+    }
+
+    /// Describe these options in a line of text, for saving
+    /// alongside the words of a BBOW: the names of the
+    /// options that are on, then `min_len=` and its value,
+    /// separated by spaces.
+    #[cfg(any(feature = "bincode", feature = "gzip"))]
+    fn describe(&self) -> String {
+        let mut options = Vec::new();
+        for (name, on) in [
+            ("case_sensitive", self.case_sensitive),
+            ("keep_contractions", self.keep_contractions),
+            ("keep_hyphens", self.keep_hyphens),
+            ("allow_digits", self.allow_digits),
+            ("ascii_only", self.ascii_only),
+        ] {
+            if on {
+                options.push(name.to_string());
+            }
+        }
+        #[cfg(feature = "unicode-normalization")]
+        if self.strip_accents {
+            options.push("strip_accents".to_string());
+        }
+        options.push(format!("min_len={}", self.min_len));
+        options.join(" ")
+    }
+
+    /// Read options written by [`describe`](Self::describe).
+    /// Returns `None` for an option that is not known, or
+    /// needs a feature that is not enabled.
+    #[cfg(any(feature = "bincode", feature = "gzip"))]
+    fn parse(text: &str) -> Option<Self> {
+        let mut config = BbowConfig::default();
+        for option in text.split(' ') {
+            if let Some(min_len) = option.strip_prefix("min_len=") {
+                config.min_len = min_len.parse().ok()?;
+                continue;
+            }
+            match option {
+                "case_sensitive" => config.case_sensitive = true,
+                "keep_contractions" => config.keep_contractions = true,
+                "keep_hyphens" => config.keep_hyphens = true,
+                "allow_digits" => config.allow_digits = true,
+                "ascii_only" => config.ascii_only = true,
+                #[cfg(feature = "unicode-normalization")]
+                "strip_accents" => config.strip_accents = true,
+                _ => return None,
+            }
+        }
+        Some(config)
+    }
+}
+
+/// The error returned by [`Bbow::try_add_text`] when
+/// adding a word would overflow its count.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CountOverflow {
+    /// The word whose count would have overflowed.
+    pub word: String,
+}
+
+impl fmt::Display for CountOverflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "count of word {:?} overflowed", self.word)
+    }
+}
+
+impl std::error::Error for CountOverflow {}
+
+/// A word is a non-empty span of letters (or digits, if
+/// `config` allows them), possibly joined by single joining
+/// characters allowed by `config`.
+fn is_word(word: &str, config: &BbowConfig) -> bool {
+    let mut previous: Option<char> = None;
+    for c in word.chars() {
+        let joins = config.is_joiner(c) && previous.is_some_and(|p| config.is_letter(p));
+        if !config.is_letter(c) && !joins {
+            return false;
+        }
+        previous = Some(c);
+    }
+    previous.is_some_and(|p| config.is_letter(p))
+}
+
+/// The characters whose full Unicode case fold differs
+/// from their lowercase form, mapped to their case fold.
+/// These are only folded with the `case-fold` feature.
+fn fold_special(c: char) -> Option<&'static str> {
+    Some(match c {
+        // 'ẞ' lowercases to 'ß'
+        'ß' => "ss",
+        'ς' => "σ",
+        'ſ' => "s",
+        'ﬀ' => "ff",
+        'ﬁ' => "fi",
+        'ﬂ' => "fl",
+        'ﬃ' => "ffi",
+        'ﬄ' => "ffl",
+        'ﬅ' | 'ﬆ' => "st",
+        'ϐ' => "β",
+        'ϑ' => "θ",
+        'ϕ' => "φ",
+        'ϖ' => "π",
+        'ϰ' => "κ",
+        'ϱ' => "ρ",
+        'ϵ' => "ε",
+        _ => return None,
+    })
+}
+
+/// Is `word` already in the lowercase (or, with the
+/// `case-fold` feature, case folded) form stored in a BBOW?
+fn is_lowercase(word: &str) -> bool {
+    let fold = cfg!(feature = "case-fold");
+    !word
+        .chars()
+        .any(|c| c.is_uppercase() || (fold && fold_special(c).is_some()))
+}
+
+/// Convert `word` to lowercase, or with the `case-fold`
+/// feature to its full case fold, borrowing it unchanged if
+/// it is already in that form.
+///
+/// This uses Unicode's default, locale-independent case
+/// mappings, with one exception: `'İ'` (capital I with dot
+/// above) becomes a plain `'i'`. Its default lowercase form
+/// is `'i'` followed by a combining dot, which is not a
+/// letter, so a word containing it could never be matched
+/// as a keyword.
+fn lowercase(word: &str) -> Cow<'_, str> {
+    if is_lowercase(word) {
+        return Cow::Borrowed(word);
+    }
+    let mut lower = word.to_lowercase();
+    if word.contains('İ') {
+        lower = lower.replace("i\u{307}", "i");
+    }
+    if !cfg!(feature = "case-fold") {
+        return Cow::Owned(lower);
+    }
+    let mut folded = String::with_capacity(lower.len());
+    for c in lower.chars() {
+        match fold_special(c) {
+            Some(fold) => folded.push_str(fold),
+            None => folded.push(c),
+        }
+    }
+    Cow::Owned(folded)
+}
+
+/// Convert `text` to Unicode Normalization Form C,
+/// borrowing it unchanged if it is already in that form.
+/// If `config` strips accents, combining marks are removed
+/// first.
+#[cfg(feature = "unicode-normalization")]
+fn compose<'t>(text: &'t str, config: &BbowConfig) -> Cow<'t, str> {
+    if config.strip_accents && !text.is_ascii() {
+        Cow::Owned(
+            text.nfd()
+                .filter(|&c| !is_combining_mark(c))
+                .nfc()
+                .collect(),
+        )
+    } else if is_nfc(text) {
+        Cow::Borrowed(text)
+    } else {
+        Cow::Owned(text.nfc().collect())
+    }
+}
+
+/// Without the `unicode-normalization` feature text is
+/// used as given.
+#[cfg(not(feature = "unicode-normalization"))]
+fn compose<'t>(text: &'t str, _config: &BbowConfig) -> Cow<'t, str> {
+    Cow::Borrowed(text)
+}
+
+/// Does `c` separate words? See the
+/// [crate documentation](crate) for the full list.
+fn is_separator(c: char) -> bool {
+    c.is_whitespace() || c == '\u{200B}'
+}
+
+/// Split `text` into the tokens that may hold words, at
+/// separators. A byte order mark at the start of `text`,
+/// as written by some Windows tools, is skipped.
+fn tokens(text: &str) -> impl Iterator<Item = &str> {
+    text.strip_prefix('\u{FEFF}')
+        .unwrap_or(text)
+        .split(is_separator)
+        .filter(|token| !token.is_empty())
+}
+
+/// Trim leading and trailing non-alphabetic characters
+/// from `token` and convert it to lowercase, giving the
+/// word it is stored as in a BBOW with the given `config`.
+/// Returns `None` if no valid word remains.
+fn normalize<'t>(token: &'t str, config: &BbowConfig) -> Option<Cow<'t, str>> {
+    if config.ascii_only {
+        return normalize_ascii(token, config);
+    }
+    // Composition must happen before trimming, since a
+    // trailing combining mark is not alphabetic.
+    match compose(token, config) {
+        Cow::Borrowed(token) => normalize_composed(token, config),
+        Cow::Owned(token) => normalize_composed(&token, config).map(|w| Cow::Owned(w.into_owned())),
+    }
+}
+
+/// [`normalize`] for an [ASCII only](BbowConfig::ascii_only)
+/// `config`, working on bytes.
+fn normalize_ascii<'t>(token: &'t str, config: &BbowConfig) -> Option<Cow<'t, str>> {
+    let is_letter = |b: u8| b.is_ascii_alphabetic() || (config.allow_digits && b.is_ascii_digit());
+    let is_joiner =
+        |b: u8| (config.keep_contractions && b == b'\'') || (config.keep_hyphens && b == b'-');
+    let bytes = token.as_bytes();
+    let start = bytes.iter().position(|&b| is_letter(b))?;
+    let end = bytes.iter().rposition(|&b| is_letter(b))? + 1;
+    // Both ends are ASCII, so they are character boundaries.
+    let word = &token[start..end];
+    let mut after_letter = false;
+    for &b in word.as_bytes() {
+        if is_letter(b) {
+            after_letter = true;
+        } else if after_letter && is_joiner(b) {
+            after_letter = false;
+        } else {
+            return None;
+        }
+    }
+    if word.len() < config.min_len {
+        return None;
+    }
+    if !config.case_sensitive && word.bytes().any(|b| b.is_ascii_uppercase()) {
+        Some(Cow::Owned(word.to_ascii_lowercase()))
+    } else {
+        Some(Cow::Borrowed(word))
+    }
+}
+
+fn normalize_composed<'t>(token: &'t str, config: &BbowConfig) -> Option<Cow<'t, str>> {
+    let word = token.trim_matches(|c: char| !config.is_letter(c));
+    if !is_word(word, config) {
+        return None;
+    }
+    // Convert to lowercase if the word contains uppercase letters.
+    let word = if !config.case_sensitive {
+        lowercase(word)
+    } else {
+        Cow::Borrowed(word)
+    };
+    let word = if config.keep_contractions && word.contains('\u{2019}') {
+        Cow::Owned(word.replace('\u{2019}', "'"))
+    } else {
+        word
+    };
+    if word.chars().count() < config.min_len {
+        return None;
+    }
+    Some(word)
+}
+
+/// Pick up to `n` of the given `(word, count)` pairs with
+/// the highest counts, in any order, sorted by descending
+/// count and then alphabetically.
+fn most_common<'w, I>(words: I, n: usize) -> Vec<(&'w str, usize)>
+where
+    I: Iterator<Item = (&'w str, usize)>,
+{
+    if n == 0 {
+        return Vec::new();
+    }
+    // Keep a heap of at most n entries whose top is the
+    // "worst" candidate so far: lowest count, and latest
+    // alphabetically among equal counts.
+    let mut heap = BinaryHeap::with_capacity(n + 1);
+    for (word, count) in words {
+        heap.push(Reverse((count, Reverse(word))));
+        if heap.len() > n {
+            heap.pop();
+        }
+    }
+    heap.into_sorted_vec()
+        .into_iter()
+        .map(|Reverse((count, Reverse(word)))| (word, count))
+        .collect()
+}
 
-fn is_word(word: &str) -> bool {
-    !word.is_empty() && word.chars().all(|c| c.is_alphabetic())
+/// A keyword can only match if it is a word in the form
+/// stored by a BBOW with the given `config`: lowercase
+/// unless case sensitive, with no punctuation.
+fn is_keyword(keyword: &str, config: &BbowConfig) -> bool {
+    is_word(keyword, config) && (config.case_sensitive || is_lowercase(keyword))
 }
 
-fn has_uppercase(word: &str) -> bool {
-    word.chars().any(char::is_uppercase)
+/// Compute the Levenshtein edit distance between two
+/// sequences of characters: the least number of single
+/// character insertions, deletions and substitutions that
+/// turn one into the other.
+fn levenshtein(a: &[char], b: &[char]) -> usize {
+    // Keep only the previous row of the distance table.
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitute = diagonal + usize::from(ca != cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitute.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
 }
 
 impl<'a> Bbow<'a> {
@@ -49,6 +536,18 @@ impl<'a> Bbow<'a> {
         Self::default()
     }
 
+    /// Make a new empty target words list that turns text
+    /// into words according to `config`.
+    pub fn with_config(config: BbowConfig) -> Self {
+        Bbow(BTreeMap::new(), config)
+    }
+
+    /// The options controlling how this BBOW turns text
+    /// into words.
+    pub fn config(&self) -> &BbowConfig {
+        &self.1
+    }
+
     /// Parse the `target` text and add the sequence of
     /// valid words contained in it to this BBOW.
     ///
@@ -65,33 +564,308 @@ impl<'a> Bbow<'a> {
     /// assert_eq!(1, bbow.match_count("hello"));
     /// ```
     pub fn extend_from_text(mut self, target: &'a str) -> Self {
+        self.add_text(target);
+        self
+    }
+
+    /// Parse each of the `texts` and add the valid words
+    /// contained in them to this BBOW, as by calling
+    /// [`extend_from_text`](Self::extend_from_text) on each
+    /// in turn.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::new().extend_from_texts(["Hello world.", "Hello again!"]);
+    /// assert_eq!(3, bbow.len());
+    /// assert_eq!(2, bbow.match_count("hello"));
+    /// ```
+    pub fn extend_from_texts<I: IntoIterator<Item = &'a str>>(self, texts: I) -> Self {
+        texts.into_iter().fold(self, Self::extend_from_text)
+    }
+
+    /// Parse the `target` text and add the sequence of
+    /// valid words contained in it to this BBOW in place.
+    ///
+    /// This follows the same rules as
+    /// [`extend_from_text`](Self::extend_from_text), but
+    /// borrows the BBOW mutably rather than consuming it,
+    /// which is more convenient when accumulating text in
+    /// a loop.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let mut bbow = Bbow::new();
+    /// for line in ["Hello world.", "Hello again!"] {
+    ///     bbow.add_text(line);
+    /// }
+    /// assert_eq!(2, bbow.match_count("hello"));
+    /// ```
+    pub fn add_text(&mut self, target: &'a str) {
         // Iterate over the words in the target text, adding them to the map.
-        for words in target.split_whitespace() {
-            // Trim leading and trailing non-alphabetic characters from the word.
-            let word = words.trim_matches(|c: char| !c.is_alphabetic());
-            if is_word(word) {
-                // Convert to lowercase if the word contains uppercase letters.
-                let cow_word = if has_uppercase(word) {
-                    Cow::Owned(word.to_lowercase())
-                } else {
-                    Cow::Borrowed(word)
-                };
-
-                // From the documentation: Add the word to the map, incrementing the count if it already exists.
-                self.0
-                    .entry(cow_word)
-                    .and_modify(|curr| *curr += 1)
-                    .or_insert(1);
+        for token in tokens(target) {
+            self.add_token(token);
+        }
+    }
+
+    /// Parse the `target` text and add the sequence of
+    /// valid words contained in it to this BBOW, as in
+    /// [`extend_from_text`](Self::extend_from_text), but
+    /// skipping words shorter than `min_len` (or the
+    /// configured [`min_len`](BbowConfig::min_len), if
+    /// larger). Length is measured in characters (Unicode
+    /// scalar values), not bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::new().extend_from_text_min_len("I am a café owner", 3);
+    /// assert_eq!(2, bbow.len());
+    /// assert_eq!(1, bbow.match_count("café"));
+    /// ```
+    pub fn extend_from_text_min_len(mut self, target: &'a str, min_len: usize) -> Self {
+        let config = BbowConfig {
+            min_len: min_len.max(self.1.min_len),
+            ..self.1
+        };
+        for token in tokens(target) {
+            if let Some(word) = normalize(token, &config) {
+                self.add_word(word);
+            }
+        }
+        self
+    }
+
+    /// Parse the `target` text and add the sequence of
+    /// valid words contained in it to this BBOW, as in
+    /// [`extend_from_text`](Self::extend_from_text), but
+    /// splitting the text at each character for which
+    /// `is_sep` returns `true` rather than at whitespace.
+    /// Whitespace then only separates words if `is_sep`
+    /// says so. Each piece is trimmed and normalized as
+    /// usual.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::new().extend_from_text_with_separators("Cat|hat;cat", |c| "|;".contains(c));
+    /// assert_eq!(2, bbow.len());
+    /// assert_eq!(2, bbow.match_count("cat"));
+    /// ```
+    pub fn extend_from_text_with_separators<F: Fn(char) -> bool>(
+        mut self,
+        target: &'a str,
+        is_sep: F,
+    ) -> Self {
+        let target = target.strip_prefix('\u{FEFF}').unwrap_or(target);
+        for token in target.split(is_sep).filter(|token| !token.is_empty()) {
+            self.add_token(token);
+        }
+        self
+    }
+
+    /// Parse the `target` text and add the sequence of
+    /// valid words contained in it to this BBOW, as in
+    /// [`extend_from_text`](Self::extend_from_text), but
+    /// splitting the work across threads.
+    ///
+    /// The text is divided into chunks at whitespace, so no
+    /// word is split between chunks. Each chunk is parsed
+    /// into its own BBOW in parallel and the results are
+    /// merged, giving exactly the same counts as parsing
+    /// sequentially.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let text = "the cat and the hat ".repeat(1000);
+    /// let bbow = Bbow::new().par_extend_from_text(&text);
+    /// assert_eq!(2000, bbow.match_count("the"));
+    /// ```
+    #[cfg(feature = "rayon")]
+    pub fn par_extend_from_text(mut self, target: &'a str) -> Self {
+        // Aim for a few chunks per thread to balance the
+        // load, but don't bother splitting small texts.
+        const MIN_CHUNK: usize = 64 * 1024;
+        let chunk_len = (target.len() / (4 * rayon::current_num_threads())).max(MIN_CHUNK);
+
+        let mut chunks = Vec::new();
+        let mut rest = target;
+        while rest.len() > chunk_len {
+            let mut end = chunk_len;
+            while !rest.is_char_boundary(end) {
+                end += 1;
+            }
+            match rest[end..].find(is_separator) {
+                Some(offset) => {
+                    let (chunk, tail) = rest.split_at(end + offset);
+                    chunks.push(chunk);
+                    rest = tail;
+                }
+                None => break,
             }
         }
+        chunks.push(rest);
+
+        let config = self.1;
+        let parsed = chunks
+            .into_par_iter()
+            .map(|chunk| {
+                let mut bbow = Bbow::with_config(config);
+                bbow.add_text(chunk);
+                bbow
+            })
+            .reduce(|| Bbow::with_config(config), |a, b| a + b);
+        self.merge(parsed);
         self
     }
 
+    /// Parse text from `reader` line by line and add the
+    /// sequence of valid words contained in it to this BBOW,
+    /// following the same rules as
+    /// [`extend_from_text`](Self::extend_from_text).
+    ///
+    /// The text is not kept around after each line is
+    /// parsed, so every word added is owned. This makes the
+    /// method best suited to a `Bbow<'static>`. Since whole
+    /// lines are read, a word is never split between reads;
+    /// for text arriving in arbitrary pieces use
+    /// [`extend_from_chunks`](Self::extend_from_chunks).
+    ///
+    /// # Errors
+    ///
+    /// Any error reading from `reader` is returned. Text
+    /// that is not valid UTF-8 gives an error of kind
+    /// [`io::ErrorKind::InvalidData`]. Words read before an
+    /// error remain in the BBOW.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let text = "Hello world.\nHello again!";
+    /// let mut bbow = Bbow::new();
+    /// bbow.extend_from_reader(text.as_bytes()).unwrap();
+    /// assert_eq!(2, bbow.match_count("hello"));
+    /// ```
+    pub fn extend_from_reader<R: BufRead>(&mut self, mut reader: R) -> io::Result<()> {
+        let mut line = String::new();
+        while reader.read_line(&mut line)? > 0 {
+            for token in tokens(&line) {
+                self.add_owned_token(token);
+            }
+            line.clear();
+        }
+        Ok(())
+    }
+
+    /// Parse text arriving as a sequence of byte `chunks`,
+    /// such as fixed-size reads from a socket, and add the
+    /// valid words contained in it to this BBOW, following
+    /// the same rules as
+    /// [`extend_from_text`](Self::extend_from_text).
+    ///
+    /// Chunk boundaries may fall anywhere, even inside a
+    /// word or a multi-byte character: the unfinished end
+    /// of each chunk is carried over to the next, and a word
+    /// is only added once whitespace or the end of the text
+    /// follows it. So the result is the same as parsing the
+    /// concatenated text in one go. As for
+    /// [`extend_from_reader`](Self::extend_from_reader),
+    /// every word added is owned.
+    ///
+    /// # Errors
+    ///
+    /// Text that is not valid UTF-8 gives an error of kind
+    /// [`io::ErrorKind::InvalidData`]. Words before the
+    /// invalid text remain in the BBOW.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let mut bbow = Bbow::new();
+    /// bbow.extend_from_chunks(["Hello wo", "rld! Hel", "lo"]).unwrap();
+    /// assert_eq!(2, bbow.match_count("hello"));
+    /// assert_eq!(1, bbow.match_count("world"));
+    /// ```
+    pub fn extend_from_chunks<I, B>(&mut self, chunks: I) -> io::Result<()>
+    where
+        I: IntoIterator<Item = B>,
+        B: AsRef<[u8]>,
+    {
+        let invalid = |e| io::Error::new(io::ErrorKind::InvalidData, e);
+        let mut pending: Vec<u8> = Vec::new();
+        for chunk in chunks {
+            pending.extend_from_slice(chunk.as_ref());
+            // A character cut off at the end of the chunk is
+            // completed by the next one.
+            let (valid, error) = match std::str::from_utf8(&pending) {
+                Ok(text) => (text, None),
+                Err(e) => (
+                    std::str::from_utf8(&pending[..e.valid_up_to()]).map_err(invalid)?,
+                    e.error_len().map(|_| e),
+                ),
+            };
+            // Only text up to the last whitespace is known to
+            // hold complete words.
+            let complete = valid
+                .char_indices()
+                .rev()
+                .find(|&(_, c)| is_separator(c))
+                .map_or(0, |(i, c)| i + c.len_utf8());
+            for token in tokens(&valid[..complete]) {
+                self.add_owned_token(token);
+            }
+            if let Some(e) = error {
+                return Err(invalid(e));
+            }
+            pending.drain(..complete);
+        }
+        let text = std::str::from_utf8(&pending).map_err(invalid)?;
+        for token in tokens(text) {
+            self.add_owned_token(token);
+        }
+        Ok(())
+    }
+
+    /// Add the word `token` normalizes to, if any.
+    fn add_token(&mut self, token: &'a str) {
+        if let Some(word) = normalize(token, &self.1) {
+            self.add_word(word);
+        }
+    }
+
+    /// Add an owned copy of the word `token` normalizes to,
+    /// if any.
+    fn add_owned_token(&mut self, token: &str) {
+        if let Some(word) = normalize(token, &self.1) {
+            self.add_word(Cow::Owned(word.into_owned()));
+        }
+    }
+
+    fn add_word(&mut self, word: Cow<'a, str>) {
+        // From the documentation: Add the word to the map, incrementing the count if it already exists.
+        self.0
+            .entry(word)
+            .and_modify(|curr| *curr += 1)
+            .or_insert(1);
+    }
+
     /// Report the number of occurrences of the given
     /// `keyword` that are indexed by this BBOW. The keyword
     /// should be lowercase and not contain punctuation, as
     /// per the rules of BBOW: otherwise the keyword will
-    /// not match and 0 will be returned.
+    /// not match and 0 will be returned. If the BBOW is
+    /// [case sensitive](BbowConfig::case_sensitive) the
+    /// keyword must match the case of the stored word
+    /// instead.
     ///
     /// # Examples:
     ///
@@ -103,16 +877,55 @@ impl<'a> Bbow<'a> {
     /// ```
     pub fn match_count(&self, keyword: &str) -> usize {
         // Check if keyword is valid
-        if !is_word(keyword) || has_uppercase(keyword) {
+        let keyword = compose(keyword, &self.1);
+        if !is_keyword(&keyword, &self.1) {
             return 0;
         }
         // Gets keyword reference from map, if it exists
         // If it doesn't exist, returns 0
         // If it does exists, copied() converts the reference to a value
-        self.0.get(keyword).copied().unwrap_or(0)
+        self.0.get(keyword.as_ref()).copied().unwrap_or(0)
     }
 
-    pub fn words(&'a self) -> impl Iterator<Item = &'a str> {
+    /// Report the number of occurrences of the given
+    /// `keyword`, after normalizing it the same way words
+    /// are normalized when parsing text: leading and
+    /// trailing non-alphabetic characters are trimmed and
+    /// the keyword is converted to lowercase.
+    ///
+    /// Unlike [`match_count`](Self::match_count), which
+    /// returns 0 for a keyword that is not already in
+    /// normalized form, this will find `"hello"` when given
+    /// `"Hello!"`. A keyword that does not normalize to a
+    /// valid word still returns 0.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::new().extend_from_text("Hello world.");
+    /// assert_eq!(0, bbow.match_count("Hello!"));
+    /// assert_eq!(1, bbow.match_count_normalized("Hello!"));
+    /// ```
+    pub fn match_count_normalized(&self, keyword: &str) -> usize {
+        normalize(keyword, &self.1)
+            .and_then(|word| self.0.get(&word).copied())
+            .unwrap_or(0)
+    }
+
+    /// Iterate over the words of this BBOW, in sorted order.
+    /// The words borrow from this BBOW, so they may outlive
+    /// neither it nor the text it was built from.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::new().extend_from_text("the cat and the hat");
+    /// let words: Vec<_> = bbow.words().collect();
+    /// assert_eq!(vec!["and", "cat", "hat", "the"], words);
+    /// ```
+    pub fn words<'b>(&'b self) -> impl Iterator<Item = &'b str> + 'b {
         self.0.keys().map(|w| w.as_ref())
     }
 
@@ -151,104 +964,4153 @@ impl<'a> Bbow<'a> {
     pub fn is_empty(&self) -> bool {
         self.0.is_empty()
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Report up to `n` of the most frequent words in this
+    /// BBOW as `(word, count)` pairs, sorted by descending
+    /// count. Words with equal counts are ordered
+    /// alphabetically.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::new()
+    ///     .extend_from_text("the cat and the hat and the bat");
+    /// assert_eq!(vec![("the", 3), ("and", 2)], bbow.most_common(2));
+    /// ```
+    pub fn most_common(&self, n: usize) -> Vec<(&str, usize)> {
+        most_common(
+            self.0.iter().map(|(word, &count)| (word.as_ref(), count)),
+            n,
+        )
+    }
 
-    #[test]
-    fn test_new_is_empty() {
-        let bbow = Bbow::new();
-        assert!(bbow.is_empty());
-        assert_eq!(bbow.len(), 0);
-        assert_eq!(bbow.count(), 0);
+    /// Add every word of `other` into this BBOW, summing
+    /// the counts of words present in both.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let mut bbow = Bbow::new().extend_from_text("the cat");
+    /// bbow.merge(Bbow::new().extend_from_text("the hat"));
+    /// assert_eq!(2, bbow.match_count("the"));
+    /// assert_eq!(4, bbow.count());
+    /// ```
+    pub fn merge(&mut self, other: Bbow<'a>) {
+        for (word, count) in other.0 {
+            *self.0.entry(word).or_insert(0) += count;
+        }
     }
 
-    // Test from documentation example
-    #[test]
-    fn test_b_banana() {
-        let bbow = Bbow::new().extend_from_text("b b b-banana b");
-        assert_eq!(0, bbow.match_count("b-banana")); // Should be zero since it has a hyphen
-        assert_eq!(3, bbow.match_count("b"));
+    /// Report whether the given `keyword` is indexed by
+    /// this BBOW. The keyword is subject to the same rules
+    /// as for [`match_count`](Self::match_count): an
+    /// invalid keyword is never contained.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::new().extend_from_text("Hello world.");
+    /// assert!(bbow.contains("hello"));
+    /// assert!(!bbow.contains("Hello"));
+    /// assert!(!bbow.contains("goodbye"));
+    /// ```
+    pub fn contains(&self, keyword: &str) -> bool {
+        let keyword = compose(keyword, &self.1);
+        is_keyword(&keyword, &self.1) && self.0.contains_key(keyword.as_ref())
     }
 
-    // Test from documentation example
-    #[test]
-    fn test_cant_stop() {
-        let bbow = Bbow::new().extend_from_text("Can't stop this! Stop!");
-        assert_eq!(2, bbow.len());
-        assert_eq!(3, bbow.count());
+    /// Report whether every one of the given `keywords` is
+    /// indexed by this BBOW, stopping at the first that is
+    /// not. Each keyword is normalized as in
+    /// [`match_count_normalized`](Self::match_count_normalized)
+    /// first; one that does not normalize to a valid word is
+    /// never contained. An empty list of keywords gives
+    /// `true`.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::new().extend_from_text("The cat and the hat");
+    /// assert!(bbow.contains_all(["Cat", "hat!"]));
+    /// assert!(!bbow.contains_all(["cat", "dog"]));
+    /// ```
+    pub fn contains_all<I: IntoIterator<Item = S>, S: AsRef<str>>(&self, keywords: I) -> bool {
+        keywords
+            .into_iter()
+            .all(|keyword| self.contains_normalized(keyword.as_ref()))
     }
 
-    #[test]
-    fn test_case_sensitivity() {
-        let bbow = Bbow::new().extend_from_text("TEST test TEst tESt");
-        assert_eq!(4, bbow.match_count("test"));
-        assert_eq!(1, bbow.len());
-        assert_eq!(4, bbow.count());
+    /// Report whether any of the given `keywords` is indexed
+    /// by this BBOW, stopping at the first that is. The
+    /// keywords are normalized as for
+    /// [`contains_all`](Self::contains_all). An empty list
+    /// of keywords gives `false`.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::new().extend_from_text("The cat and the hat");
+    /// assert!(bbow.contains_any(["dog", "Cat"]));
+    /// assert!(!bbow.contains_any(["dog", "bird"]));
+    /// ```
+    pub fn contains_any<I: IntoIterator<Item = S>, S: AsRef<str>>(&self, keywords: I) -> bool {
+        keywords
+            .into_iter()
+            .any(|keyword| self.contains_normalized(keyword.as_ref()))
     }
 
-    #[test]
+    /// Is the word `keyword` normalizes to, if any, indexed
+    /// by this BBOW?
+    fn contains_normalized(&self, keyword: &str) -> bool {
+        normalize(keyword, &self.1).is_some_and(|word| self.0.contains_key(word.as_ref()))
+    }
+
+    /// Convert this BBOW into one that owns all of its
+    /// words, so that it no longer borrows from the text it
+    /// was built from.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// fn parse(text: String) -> Bbow<'static> {
+    ///     Bbow::new().extend_from_text(&text).into_owned()
+    /// }
+    /// let bbow = parse("Hello world.".to_string());
+    /// assert_eq!(1, bbow.match_count("hello"));
+    /// ```
+    pub fn into_owned(self) -> Bbow<'static> {
+        Bbow(
+            self.0
+                .into_iter()
+                .map(|(word, count)| (Cow::Owned(word.into_owned()), count))
+                .collect(),
+            self.1,
+        )
+    }
+
+    /// Remove the given `keyword` from this BBOW, returning
+    /// the number of occurrences removed. The keyword is
+    /// subject to the same rules as for
+    /// [`match_count`](Self::match_count): 0 is returned and
+    /// nothing is removed if it is invalid or absent.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let mut bbow = Bbow::new().extend_from_text("um the um cat");
+    /// assert_eq!(2, bbow.remove("um"));
+    /// assert!(!bbow.contains("um"));
+    /// assert_eq!(2, bbow.count());
+    /// ```
+    pub fn remove(&mut self, keyword: &str) -> usize {
+        let keyword = compose(keyword, &self.1);
+        if !is_keyword(&keyword, &self.1) {
+            return 0;
+        }
+        self.0.remove(keyword.as_ref()).unwrap_or(0)
+    }
+
+    /// Remove one occurrence of the given `keyword` from
+    /// this BBOW, returning its new count. A word whose
+    /// count reaches zero is removed. The keyword is subject
+    /// to the same rules as for
+    /// [`match_count`](Self::match_count): 0 is returned and
+    /// nothing is changed if it is invalid or absent.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let mut bbow = Bbow::new().extend_from_text("um the um cat");
+    /// assert_eq!(1, bbow.decrement("um"));
+    /// assert_eq!(0, bbow.decrement("um"));
+    /// assert!(!bbow.contains("um"));
+    /// ```
+    pub fn decrement(&mut self, keyword: &str) -> usize {
+        let keyword = compose(keyword, &self.1);
+        if !is_keyword(&keyword, &self.1) {
+            return 0;
+        }
+        let Some(count) = self.0.get_mut(keyword.as_ref()) else {
+            return 0;
+        };
+        *count -= 1;
+        let count = *count;
+        if count == 0 {
+            self.0.remove(keyword.as_ref());
+        }
+        count
+    }
+
+    /// Keep only the words for which the predicate `f`
+    /// returns `true`. The predicate is given each word and
+    /// its count.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let mut bbow = Bbow::new().extend_from_text("a cat and a hat");
+    /// bbow.retain(|word, _| word.chars().count() > 1);
+    /// assert!(!bbow.contains("a"));
+    /// assert_eq!(3, bbow.len());
+    /// ```
+    pub fn retain<F: FnMut(&str, usize) -> bool>(&mut self, mut f: F) {
+        self.0.retain(|word, count| f(word, *count));
+    }
+
+    /// Release unused memory. A [`BTreeMap`] frees its nodes
+    /// as words are removed, so this does nothing; it is
+    /// provided to match [`HashedBbow::shrink_to_fit`].
+    pub fn shrink_to_fit(&mut self) {}
+
+    /// Remove all words from this BBOW, leaving it empty.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let mut bbow = Bbow::new().extend_from_text("Hello world.");
+    /// bbow.clear();
+    /// assert!(bbow.is_empty());
+    /// ```
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    /// Render this BBOW as CSV: a `word,count` header
+    /// followed by one `word,count` row per word, in sorted
+    /// word order. Each line ends with `\n`. A word
+    /// containing a comma, double quote or line break is
+    /// quoted as per RFC 4180.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::new().extend_from_text("the cat the hat");
+    /// assert_eq!("word,count\ncat,1\nhat,1\nthe,2\n", bbow.to_csv());
+    /// ```
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("word,count\n");
+        for (word, count) in &self.0 {
+            if word.contains([',', '"', '\r', '\n']) {
+                csv.push('"');
+                csv.push_str(&word.replace('"', "\"\""));
+                csv.push('"');
+            } else {
+                csv.push_str(word);
+            }
+            csv.push(',');
+            csv.push_str(&count.to_string());
+            csv.push('\n');
+        }
+        csv
+    }
+
+    /// Remove every word in the builtin list of
+    /// [English stopwords](stopwords::ENGLISH) from this
+    /// BBOW. Words are compared in lowercase, so in a
+    /// [case sensitive](BbowConfig::case_sensitive) BBOW
+    /// `"The"` and `"THE"` are removed along with `"the"`.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let mut bbow = Bbow::new().extend_from_text("The cat and the hat");
+    /// bbow.remove_stopwords_en();
+    /// assert_eq!(2, bbow.count());
+    /// ```
+    #[cfg(feature = "stopwords")]
+    pub fn remove_stopwords_en(&mut self) {
+        // The list is sorted, so it can be searched.
+        self.0.retain(|word, _| {
+            stopwords::ENGLISH
+                .binary_search(&&*lowercase(word))
+                .is_err()
+        });
+    }
+
+    /// Remove each of the given `words` from this BBOW.
+    /// Each word is normalized as in
+    /// [`match_count_normalized`](Self::match_count_normalized)
+    /// first; words that are not present are ignored.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let mut bbow = Bbow::new().extend_from_text("Lorem ipsum dolor sit amet");
+    /// bbow.remove_words(["Lorem", "ipsum", "consectetur"]);
+    /// assert_eq!(3, bbow.len());
+    /// ```
+    pub fn remove_words<I: IntoIterator<Item = S>, S: AsRef<str>>(&mut self, words: I) {
+        for word in words {
+            if let Some(word) = normalize(word.as_ref(), &self.1) {
+                self.0.remove(word.as_ref());
+            }
+        }
+    }
+
+    /// Compute the cosine similarity of this BBOW and
+    /// `other`, treating each as a vector of word counts.
+    /// The result is between 0.0 (no words in common) and
+    /// 1.0 (the same words in the same proportions). If
+    /// either BBOW is empty the result is 0.0.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let a = Bbow::new().extend_from_text("the cat");
+    /// let b = Bbow::new().extend_from_text("the the cat cat");
+    /// assert!((a.cosine_similarity(&b) - 1.0).abs() < 1e-12);
+    /// ```
+    pub fn cosine_similarity(&self, other: &Bbow) -> f64 {
+        if self.is_empty() || other.is_empty() {
+            return 0.0;
+        }
+        let dot: f64 = self
+            .0
+            .iter()
+            .filter_map(|(word, &count)| {
+                other
+                    .0
+                    .get(word)
+                    .map(|&other_count| count as f64 * other_count as f64)
+            })
+            .sum();
+        let norm = |bbow: &Bbow| {
+            bbow.0
+                .values()
+                .map(|&count| count as f64 * count as f64)
+                .sum::<f64>()
+                .sqrt()
+        };
+        // Rounding can push the result of comparing a bag
+        // with itself slightly past 1.0.
+        (dot / (norm(self) * norm(other))).min(1.0)
+    }
+
+    /// Compute the Jaccard similarity of the vocabularies
+    /// of this BBOW and `other`: the number of words in
+    /// both divided by the number of words in either.
+    /// Counts are ignored. The result is between 0.0 (no
+    /// words in common) and 1.0 (the same words). Two empty
+    /// BBOWs have the same (empty) vocabulary, so their
+    /// similarity is 1.0.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let a = Bbow::new().extend_from_text("the cat sat");
+    /// let b = Bbow::new().extend_from_text("the cat cat ran");
+    /// assert_eq!(0.5, a.jaccard_similarity(&b));
+    /// ```
+    pub fn jaccard_similarity(&self, other: &Bbow) -> f64 {
+        let shared = self.shared_len(other);
+        let union = self.len() + other.len() - shared;
+        if union == 0 {
+            return 1.0;
+        }
+        shared as f64 / union as f64
+    }
+
+    /// Compute the Dice coefficient of the vocabularies of
+    /// this BBOW and `other`: twice the number of words in
+    /// both, divided by the sum of their numbers of unique
+    /// words. Counts are ignored. The result is between 0.0
+    /// (no words in common) and 1.0 (the same words). As for
+    /// [`jaccard_similarity`](Self::jaccard_similarity), two
+    /// empty BBOWs have a coefficient of 1.0.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let a = Bbow::new().extend_from_text("the cat sat");
+    /// let b = Bbow::new().extend_from_text("the cat cat ran");
+    /// assert_eq!(2.0 / 3.0, a.dice_coefficient(&b));
+    /// ```
+    pub fn dice_coefficient(&self, other: &Bbow) -> f64 {
+        let total = self.len() + other.len();
+        if total == 0 {
+            return 1.0;
+        }
+        (2 * self.shared_len(other)) as f64 / total as f64
+    }
+
+    /// Compute the Euclidean distance between this BBOW and
+    /// `other`, treating each as a vector of word counts
+    /// over the words in either: the square root of the sum
+    /// of the squared differences of the counts of each
+    /// word. Identical BBOWs have a distance of 0.0.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let a = Bbow::new().extend_from_text("the cat");
+    /// let b = Bbow::new().extend_from_text("the the the dog");
+    /// // (3 - 1)² + 1² + 1² = 6
+    /// assert_eq!(6.0f64.sqrt(), a.euclidean_distance(&b));
+    /// ```
+    pub fn euclidean_distance(&self, other: &Bbow) -> f64 {
+        // Walk both sorted maps together, visiting each word
+        // of either once.
+        let mut ours = self.0.iter().peekable();
+        let mut theirs = other.0.iter().peekable();
+        let mut sum = 0.0;
+        loop {
+            let difference = match (ours.peek(), theirs.peek()) {
+                (Some((a, &x)), Some((b, &y))) => match a.cmp(b) {
+                    Ordering::Less => {
+                        ours.next();
+                        x
+                    }
+                    Ordering::Greater => {
+                        theirs.next();
+                        y
+                    }
+                    Ordering::Equal => {
+                        ours.next();
+                        theirs.next();
+                        x.abs_diff(y)
+                    }
+                },
+                (Some((_, &x)), None) => {
+                    ours.next();
+                    x
+                }
+                (None, Some((_, &y))) => {
+                    theirs.next();
+                    y
+                }
+                (None, None) => break,
+            } as f64;
+            sum += difference * difference;
+        }
+        sum.sqrt()
+    }
+
+    /// Count the words present in both this BBOW and
+    /// `other`.
+    fn shared_len(&self, other: &Bbow) -> usize {
+        let (small, large) = if self.len() <= other.len() {
+            (&self.0, &other.0)
+        } else {
+            (&other.0, &self.0)
+        };
+        small
+            .keys()
+            .filter(|word| large.contains_key(*word))
+            .count()
+    }
+
+    /// Make a new BBOW holding the words present in either
+    /// this BBOW or `other`, each with the sum of its two
+    /// counts, leaving both unchanged. This is multiset sum,
+    /// as for [`merge`](Self::merge) but without consuming
+    /// `other`.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let a = Bbow::new().extend_from_text("the cat");
+    /// let b = Bbow::new().extend_from_text("the hat");
+    /// let all = a.union(&b);
+    /// assert_eq!(2, all.match_count("the"));
+    /// assert_eq!(3, all.len());
+    /// ```
+    pub fn union(&self, other: &Bbow<'a>) -> Bbow<'a> {
+        let mut words = self.0.clone();
+        for (word, &count) in &other.0 {
+            *words.entry(word.clone()).or_insert(0) += count;
+        }
+        Bbow(words, self.1)
+    }
+
+    /// Make a new BBOW holding the words present in both
+    /// this BBOW and `other`, each with the smaller of its
+    /// two counts. This is multiset intersection.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let a = Bbow::new().extend_from_text("the the cat");
+    /// let b = Bbow::new().extend_from_text("the hat");
+    /// let common = a.intersection(&b);
+    /// assert_eq!(1, common.match_count("the"));
+    /// assert_eq!(1, common.len());
+    /// ```
+    pub fn intersection(&self, other: &Bbow<'a>) -> Bbow<'a> {
+        let words = self
+            .0
+            .iter()
+            .filter_map(|(word, &count)| {
+                other
+                    .0
+                    .get(word)
+                    .map(|&other_count| (word.clone(), count.min(other_count)))
+            })
+            .collect();
+        Bbow(words, self.1)
+    }
+
+    /// Make a new BBOW holding the words of this BBOW with
+    /// their counts reduced by their counts in `other`.
+    /// Words whose count drops to zero are left out, and
+    /// words only in `other` are ignored. This is multiset
+    /// difference.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let a = Bbow::new().extend_from_text("the the cat");
+    /// let b = Bbow::new().extend_from_text("the cat hat");
+    /// let rest = a.difference(&b);
+    /// assert_eq!(1, rest.match_count("the"));
+    /// assert_eq!(1, rest.len());
+    /// ```
+    pub fn difference(&self, other: &Bbow<'a>) -> Bbow<'a> {
+        let words = self
+            .0
+            .iter()
+            .filter_map(|(word, &count)| {
+                let other_count = other.0.get(word).copied().unwrap_or(0);
+                let count = count.saturating_sub(other_count);
+                (count > 0).then(|| (word.clone(), count))
+            })
+            .collect();
+        Bbow(words, self.1)
+    }
+
+    /// Report whether every word of this BBOW appears in
+    /// `other` with at least as large a count. An empty
+    /// BBOW is a subset of every BBOW.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let a = Bbow::new().extend_from_text("the cat");
+    /// let b = Bbow::new().extend_from_text("the cat and the hat");
+    /// assert!(a.is_subset(&b));
+    /// assert!(!b.is_subset(&a));
+    /// ```
+    pub fn is_subset(&self, other: &Bbow) -> bool {
+        self.len() <= other.len()
+            && self
+                .0
+                .iter()
+                .all(|(word, &count)| other.0.get(word).is_some_and(|&c| c >= count))
+    }
+
+    /// Report whether every word of `other` appears in this
+    /// BBOW with at least as large a count. Every BBOW is a
+    /// superset of an empty BBOW.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let a = Bbow::new().extend_from_text("the cat and the hat");
+    /// let b = Bbow::new().extend_from_text("the the");
+    /// assert!(a.is_superset(&b));
+    /// ```
+    pub fn is_superset(&self, other: &Bbow) -> bool {
+        other.is_subset(self)
+    }
+
+    /// Iterate over the words of this BBOW that start with
+    /// `prefix`, with their counts, in sorted order. The
+    /// prefix is matched as given, so it should be in the
+    /// same form as the stored words. Only the matching
+    /// words are visited.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::new().extend_from_text("cat café dog cat");
+    /// let words: Vec<_> = bbow.words_with_prefix("ca").collect();
+    /// assert_eq!(vec![("café", 1), ("cat", 2)], words);
+    /// ```
+    pub fn words_with_prefix(&self, prefix: &str) -> impl Iterator<Item = (&str, usize)> + '_ {
+        let prefix = prefix.to_owned();
+        self.0
+            .range::<str, _>((Bound::Included(prefix.as_str()), Bound::Unbounded))
+            .take_while(move |(word, _)| word.starts_with(&prefix))
+            .map(|(word, &count)| (word.as_ref(), count))
+    }
+
+    /// Count the overall number of occurrences of words in
+    /// this BBOW that start with `prefix`. As for
+    /// [`words_with_prefix`](Self::words_with_prefix), the
+    /// prefix is matched as given and only the matching
+    /// words are visited.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::new().extend_from_text("internal intern interval other intern");
+    /// assert_eq!(4, bbow.prefix_count("inter"));
+    /// ```
+    pub fn prefix_count(&self, prefix: &str) -> usize {
+        self.0
+            .range::<str, _>((Bound::Included(prefix), Bound::Unbounded))
+            .take_while(|(word, _)| word.starts_with(prefix))
+            .map(|(_, &count)| count)
+            .sum()
+    }
+
+    /// Find the word in this BBOW closest to `query` by
+    /// Levenshtein edit distance, counted in characters, as
+    /// long as that distance is at most `max_distance`. The
+    /// query is composed as for
+    /// [`match_count`](Self::match_count) and converted to
+    /// lowercase first, unless the BBOW is
+    /// [case sensitive](BbowConfig::case_sensitive).
+    /// Among equally close words the alphabetically first is
+    /// chosen.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::new().extend_from_text("hello help world");
+    /// assert_eq!(Some("hello"), bbow.nearest("Helo", 1));
+    /// assert_eq!(None, bbow.nearest("xyz", 2));
+    /// ```
+    pub fn nearest(&self, query: &str, max_distance: usize) -> Option<&str> {
+        let query = compose(query, &self.1);
+        let query: Vec<char> = if self.1.case_sensitive {
+            query.chars().collect()
+        } else {
+            lowercase(&query).chars().collect()
+        };
+        let mut best: Option<(usize, &str)> = None;
+        for word in self.0.keys() {
+            let bound = best.map_or(max_distance, |(distance, _)| distance - 1);
+            let chars: Vec<char> = word.chars().collect();
+            // The distance is at least the difference in length.
+            if chars.len().abs_diff(query.len()) > bound {
+                continue;
+            }
+            let distance = levenshtein(&query, &chars);
+            if distance <= bound {
+                best = Some((distance, word));
+                if distance == 0 {
+                    break;
+                }
+            }
+        }
+        best.map(|(_, word)| word)
+    }
+
+    /// Compute the type-token ratio of this BBOW: the number
+    /// of unique words divided by the overall number of
+    /// words. An empty BBOW has a ratio of 0.0.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::new().extend_from_text("the cat and the hat");
+    /// assert_eq!(0.8, bbow.type_token_ratio());
+    /// ```
+    pub fn type_token_ratio(&self) -> f64 {
+        if self.is_empty() {
+            return 0.0;
+        }
+        self.len() as f64 / self.count() as f64
+    }
+
+    /// Count the hapax legomena of this BBOW: the words that
+    /// occur exactly once.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::new().extend_from_text("the cat and the hat");
+    /// assert_eq!(3, bbow.hapax_count());
+    /// ```
+    pub fn hapax_count(&self) -> usize {
+        self.0.values().filter(|&&count| count == 1).count()
+    }
+
+    /// Compute the Shannon entropy, in bits, of the
+    /// distribution of words in this BBOW, where each word's
+    /// probability is its count divided by the overall
+    /// number of words. An empty BBOW, or one with a single
+    /// unique word, has an entropy of 0.0.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::new().extend_from_text("heads tails");
+    /// assert_eq!(1.0, bbow.entropy());
+    /// ```
+    pub fn entropy(&self) -> f64 {
+        let total = self.count() as f64;
+        self.0
+            .values()
+            .map(|&count| {
+                let p = count as f64 / total;
+                -p * p.log2()
+            })
+            .sum()
+    }
+
+    /// Map each word length in this BBOW to the number of
+    /// unique words of that length. Length is measured in
+    /// characters (Unicode scalar values), not bytes.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::new().extend_from_text("a cat and a hat");
+    /// let histogram = bbow.length_histogram();
+    /// assert_eq!(Some(&1), histogram.get(&1));
+    /// assert_eq!(Some(&3), histogram.get(&3));
+    /// ```
+    pub fn length_histogram(&self) -> BTreeMap<usize, usize> {
+        let mut histogram = BTreeMap::new();
+        for word in self.0.keys() {
+            *histogram.entry(word.chars().count()).or_insert(0) += 1;
+        }
+        histogram
+    }
+
+    /// Map each word length in this BBOW to the overall
+    /// number of occurrences of words of that length, as in
+    /// [`length_histogram`](Self::length_histogram) but
+    /// counting multiple occurrences separately.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::new().extend_from_text("a cat and a hat");
+    /// let histogram = bbow.length_histogram_weighted();
+    /// assert_eq!(Some(&2), histogram.get(&1));
+    /// assert_eq!(Some(&3), histogram.get(&3));
+    /// ```
+    pub fn length_histogram_weighted(&self) -> BTreeMap<usize, usize> {
+        let mut histogram = BTreeMap::new();
+        for (word, &count) in &self.0 {
+            *histogram.entry(word.chars().count()).or_insert(0) += count;
+        }
+        histogram
+    }
+
+    /// Report the relative frequency of the given `keyword`
+    /// in this BBOW: its count divided by the overall number
+    /// of words. The keyword is subject to the same rules as
+    /// for [`match_count`](Self::match_count). Returns 0.0
+    /// for an absent or invalid keyword, or an empty BBOW.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::new().extend_from_text("the cat and the hat");
+    /// assert_eq!(0.4, bbow.probability("the"));
+    /// ```
+    pub fn probability(&self, keyword: &str) -> f64 {
+        match self.match_count(keyword) {
+            0 => 0.0,
+            count => count as f64 / self.count() as f64,
+        }
+    }
+
+    /// Report the count of keyword `a` divided by the count
+    /// of keyword `b`. Both keywords are subject to the same
+    /// rules as for [`match_count`](Self::match_count):
+    /// `None` is returned if either is invalid, or if `b` is
+    /// absent. An absent `a` gives `Some(0.0)`.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::new().extend_from_text("the cat and the hat");
+    /// assert_eq!(Some(2.0), bbow.count_ratio("the", "cat"));
+    /// assert_eq!(None, bbow.count_ratio("the", "dog"));
+    /// ```
+    pub fn count_ratio(&self, a: &str, b: &str) -> Option<f64> {
+        let b = self.get(b)?;
+        if !is_keyword(&compose(a, &self.1), &self.1) {
+            return None;
+        }
+        Some(self.match_count(a) as f64 / b as f64)
+    }
+
+    /// Compute the Kullback-Leibler divergence, in bits, of
+    /// the word distribution of `other` from that of this
+    /// BBOW: `Σ p(w) log2(p(w) / q(w))` over the words `w`
+    /// of this BBOW, where `p` and `q` are the relative
+    /// frequencies in this BBOW and `other`.
+    ///
+    /// A word of this BBOW missing from `other` would make
+    /// the divergence infinite, so `q` is smoothed by adding
+    /// one to the count of every word in either BBOW; see
+    /// [`kl_divergence_smoothed`](Self::kl_divergence_smoothed).
+    /// An empty BBOW has a divergence of 0.0.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let a = Bbow::new().extend_from_text("the cat");
+    /// assert_eq!(0.0, a.kl_divergence(&a));
+    /// let b = Bbow::new().extend_from_text("the dog");
+    /// assert!(a.kl_divergence(&b) > 0.0);
+    /// ```
+    pub fn kl_divergence(&self, other: &Bbow) -> f64 {
+        self.kl_divergence_smoothed(other, 1.0)
+    }
+
+    /// Compute the Kullback-Leibler divergence as for
+    /// [`kl_divergence`](Self::kl_divergence), using
+    /// additive smoothing with the given `epsilon`: with
+    /// `V` the number of unique words in either BBOW,
+    ///
+    /// `q(w) = (count of w in other + epsilon) / (other.count() + epsilon * V)`
+    ///
+    /// An `epsilon` of 0.0 disables smoothing, in which case
+    /// the divergence is infinite if some word of this BBOW
+    /// is missing from `other`, as when `other` is empty.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let a = Bbow::new().extend_from_text("heads tails");
+    /// let b = Bbow::new().extend_from_text("heads heads heads tails");
+    /// let d = a.kl_divergence_smoothed(&b, 0.0);
+    /// assert!((d - 0.5 * (4.0f64 / 3.0).log2()).abs() < 1e-12);
+    /// ```
+    pub fn kl_divergence_smoothed(&self, other: &Bbow, epsilon: f64) -> f64 {
+        if self.is_empty() {
+            return 0.0;
+        }
+        let total = self.count() as f64;
+        let vocabulary = self.len() + other.len() - self.shared_len(other);
+        let other_total = other.count() as f64 + epsilon * vocabulary as f64;
+        // Every word of this BBOW is missing from an empty
+        // unsmoothed `other`: avoid dividing 0.0 by 0.0.
+        if other_total == 0.0 {
+            return f64::INFINITY;
+        }
+        self.0
+            .iter()
+            .map(|(word, &count)| {
+                let p = count as f64 / total;
+                let other_count = other.0.get(word).copied().unwrap_or(0);
+                let q = (other_count as f64 + epsilon) / other_total;
+                p * (p / q).log2()
+            })
+            .sum()
+    }
+
+    /// Draw a random word from this BBOW using `rng`, with
+    /// each word chosen with probability proportional to its
+    /// count. Returns `None` for an empty BBOW.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::new().extend_from_text("the cat and the hat");
+    /// let word = bbow.sample(&mut rand::thread_rng()).unwrap();
+    /// assert!(bbow.contains(word));
+    /// ```
+    #[cfg(feature = "rand")]
+    pub fn sample<R: rand::Rng>(&self, rng: &mut R) -> Option<&str> {
+        let total = self.count();
+        if total == 0 {
+            return None;
+        }
+        // Scan for the word whose span of cumulative counts
+        // contains the target.
+        let mut target = rng.gen_range(0..total);
+        for (word, &count) in &self.0 {
+            if target < count {
+                return Some(word);
+            }
+            target -= count;
+        }
+        unreachable!("target is less than the total count")
+    }
+
+    /// Report the number of occurrences of the given
+    /// `keyword`, or `None` if it is not in this BBOW. The
+    /// keyword is subject to the same rules as for
+    /// [`match_count`](Self::match_count): `None` is
+    /// returned if it is invalid.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::new().extend_from_text("a cat, a hat");
+    /// assert_eq!(Some(2), bbow.get("a"));
+    /// assert_eq!(None, bbow.get("dog"));
+    /// ```
+    pub fn get(&self, keyword: &str) -> Option<usize> {
+        let keyword = compose(keyword, &self.1);
+        if !is_keyword(&keyword, &self.1) {
+            return None;
+        }
+        self.0.get(keyword.as_ref()).copied()
+    }
+
+    /// Set the count of the word `word` normalizes to,
+    /// as for a token of [`extend_from_text`], returning its
+    /// previous count if it was present. Setting a count of
+    /// 0 removes the word, since a BBOW never stores zero
+    /// counts. If `word` is not a valid word the BBOW is
+    /// left unchanged and `None` is returned.
+    ///
+    /// [`extend_from_text`]: Self::extend_from_text
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let mut bbow = Bbow::new();
+    /// assert_eq!(None, bbow.insert("Cat", 3));
+    /// assert_eq!(Some(3), bbow.insert("cat", 5));
+    /// assert_eq!(5, bbow.match_count("cat"));
+    /// assert_eq!(Some(5), bbow.insert("cat", 0));
+    /// assert!(bbow.is_empty());
+    /// ```
+    pub fn insert(&mut self, word: &'a str, count: usize) -> Option<usize> {
+        let word = normalize(word, &self.1)?;
+        if count == 0 {
+            self.0.remove(word.as_ref())
+        } else {
+            self.0.insert(word, count)
+        }
+    }
+
+    /// Multiply the count of every word by `factor`, for
+    /// instance to weight a document before merging it.
+    /// Scaling by 0 empties the BBOW, since a BBOW never
+    /// stores zero counts.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let mut bbow = Bbow::new().extend_from_text("a cat, a hat");
+    /// bbow.scale(2);
+    /// assert_eq!(4, bbow.match_count("a"));
+    /// assert_eq!(8, bbow.count());
+    /// ```
+    pub fn scale(&mut self, factor: usize) {
+        if factor == 0 {
+            self.0.clear();
+        }
+        for count in self.0.values_mut() {
+            *count *= factor;
+        }
+    }
+
+    /// Parse the `target` text and add its valid words to
+    /// this BBOW in place, as in [`add_text`](Self::add_text),
+    /// but checking each count for overflow rather than
+    /// wrapping around. If a count would overflow, a
+    /// [`CountOverflow`] error naming the word is returned;
+    /// the words before it in `target` have been added, and
+    /// the BBOW is otherwise unchanged.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let mut bbow = Bbow::new();
+    /// bbow.insert("the", usize::MAX);
+    /// assert!(bbow.try_add_text("a cat").is_ok());
+    /// let err = bbow.try_add_text("the cat").unwrap_err();
+    /// assert_eq!("the", err.word);
+    /// ```
+    pub fn try_add_text(&mut self, target: &'a str) -> Result<(), CountOverflow> {
+        for token in tokens(target) {
+            let Some(word) = normalize(token, &self.1) else {
+                continue;
+            };
+            match self.0.get_mut(word.as_ref()) {
+                Some(count) => {
+                    *count = count.checked_add(1).ok_or_else(|| CountOverflow {
+                        word: word.to_string(),
+                    })?;
+                }
+                None => {
+                    self.0.insert(word, 1);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Remove all words from this BBOW, returning an
+    /// iterator over the removed words and their counts in
+    /// alphabetical order. The BBOW is emptied straight
+    /// away, even if the iterator is not consumed.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let mut bbow = Bbow::new().extend_from_text("b a b");
+    /// let words: Vec<_> = bbow.drain().collect();
+    /// assert_eq!(vec![("a".into(), 1), ("b".into(), 2)], words);
+    /// assert!(bbow.is_empty());
+    /// ```
+    pub fn drain(&mut self) -> impl Iterator<Item = (Cow<'a, str>, usize)> {
+        std::mem::take(&mut self.0).into_iter()
+    }
+
+    /// Report the most frequent word in this BBOW with its
+    /// count, or `None` if the BBOW is empty. Of words with
+    /// equal counts the alphabetically first is chosen, as
+    /// for [`most_common`](Self::most_common).
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::new().extend_from_text("the cat and the hat");
+    /// assert_eq!(Some(("the", 2)), bbow.mode());
+    /// ```
+    pub fn mode(&self) -> Option<(&str, usize)> {
+        let mut mode: Option<(&str, usize)> = None;
+        // Words are visited in alphabetical order, so only a
+        // strictly greater count replaces the current mode.
+        for (word, &count) in &self.0 {
+            if mode.is_none_or(|(_, max)| count > max) {
+                mode = Some((word, count));
+            }
+        }
+        mode
+    }
+
+    /// Report the count of each word against its rank, for
+    /// instance to check a text against Zipf's law. The
+    /// result is a `(rank, count)` pair for every word,
+    /// sorted by descending count, where the most frequent
+    /// word has rank 1. Words with equal counts get
+    /// consecutive ranks in alphabetical order, as for
+    /// [`most_common`](Self::most_common).
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::new().extend_from_text("the cat and the hat");
+    /// assert_eq!(
+    ///     vec![(1, 2), (2, 1), (3, 1), (4, 1)],
+    ///     bbow.rank_frequency()
+    /// );
+    /// ```
+    pub fn rank_frequency(&self) -> Vec<(usize, usize)> {
+        let mut counts: Vec<usize> = self.0.values().copied().collect();
+        counts.sort_unstable_by(|a, b| b.cmp(a));
+        (1..).zip(counts).collect()
+    }
+
+    /// Iterate in alphabetical order over the words that
+    /// occur exactly `n` times in this BBOW. Words with a
+    /// count of 1 are those counted by
+    /// [`hapax_count`](Self::hapax_count); since a BBOW
+    /// never stores zero counts, `n == 0` yields nothing.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::new().extend_from_text("the cat and the hat");
+    /// let hapaxes: Vec<_> = bbow.words_with_count(1).collect();
+    /// assert_eq!(vec!["and", "cat", "hat"], hapaxes);
+    /// ```
+    pub fn words_with_count(&self, n: usize) -> impl Iterator<Item = &str> + '_ {
+        self.0
+            .iter()
+            .filter(move |&(_, &count)| count == n)
+            .map(|(word, _)| word.as_ref())
+    }
+
+    /// Count the overall number of characters (Unicode
+    /// scalar values, not bytes) in the words of this BBOW,
+    /// counting each occurrence of a word separately.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::new().extend_from_text("the café, the end");
+    /// assert_eq!(13, bbow.total_chars());
+    /// ```
+    pub fn total_chars(&self) -> usize {
+        self.0
+            .iter()
+            .map(|(word, count)| word.chars().count() * count)
+            .sum()
+    }
+
+    /// Compute the mean length in characters of the words
+    /// in this BBOW, counting each occurrence of a word
+    /// separately: the [`total_chars`](Self::total_chars)
+    /// divided by the [`count`](Self::count). An empty BBOW
+    /// has an average word length of 0.0.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::new().extend_from_text("a cat, a hat");
+    /// assert_eq!(2.0, bbow.average_word_length());
+    /// ```
+    pub fn average_word_length(&self) -> f64 {
+        if self.is_empty() {
+            return 0.0;
+        }
+        self.total_chars() as f64 / self.count() as f64
+    }
+
+    /// Report the longest word in this BBOW, measured in
+    /// characters, or `None` if the BBOW is empty. Of words
+    /// with equal lengths the alphabetically first is
+    /// chosen.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::new().extend_from_text("a cat in a hat");
+    /// assert_eq!(Some("cat"), bbow.longest_word());
+    /// ```
+    pub fn longest_word(&self) -> Option<&str> {
+        self.extreme_word(|length, best| length > best)
+    }
+
+    /// Report the shortest word in this BBOW, measured in
+    /// characters, or `None` if the BBOW is empty. Of words
+    /// with equal lengths the alphabetically first is
+    /// chosen.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::new().extend_from_text("the cat in a hat");
+    /// assert_eq!(Some("a"), bbow.shortest_word());
+    /// ```
+    pub fn shortest_word(&self) -> Option<&str> {
+        self.extreme_word(|length, best| length < best)
+    }
+
+    /// Find the alphabetically first word whose length in
+    /// characters no other word's length `beats`.
+    fn extreme_word(&self, beats: impl Fn(usize, usize) -> bool) -> Option<&str> {
+        let mut extreme: Option<(&str, usize)> = None;
+        for word in self.0.keys() {
+            let length = word.chars().count();
+            if extreme.is_none_or(|(_, best)| beats(length, best)) {
+                extreme = Some((word, length));
+            }
+        }
+        extreme.map(|(word, _)| word)
+    }
+
+    /// Group the words of this BBOW by their first
+    /// character, as for an alphabetical index. Each
+    /// character maps to the words starting with it, in
+    /// alphabetical order. Words are grouped by their
+    /// stored form, so unless the BBOW is
+    /// [case sensitive](BbowConfig::case_sensitive) the
+    /// characters are lowercase.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::new().extend_from_text("Cat bat cow");
+    /// let groups = bbow.group_by_first_char();
+    /// assert_eq!(vec!["bat"], groups[&'b']);
+    /// assert_eq!(vec!["cat", "cow"], groups[&'c']);
+    /// ```
+    pub fn group_by_first_char(&self) -> BTreeMap<char, Vec<&str>> {
+        let mut groups: BTreeMap<char, Vec<&str>> = BTreeMap::new();
+        for word in self.0.keys() {
+            // Words are never empty.
+            if let Some(first) = word.chars().next() {
+                groups.entry(first).or_default().push(word);
+            }
+        }
+        groups
+    }
+
+    /// Count the distinct words of this BBOW starting with
+    /// each character, as for
+    /// [`group_by_first_char`](Self::group_by_first_char)
+    /// but without collecting the words themselves.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::new().extend_from_text("Cat bat cow cat");
+    /// let counts = bbow.first_char_counts();
+    /// assert_eq!(1, counts[&'b']);
+    /// assert_eq!(2, counts[&'c']);
+    /// ```
+    pub fn first_char_counts(&self) -> BTreeMap<char, usize> {
+        let mut counts = BTreeMap::new();
+        for word in self.0.keys() {
+            // Words are never empty.
+            if let Some(first) = word.chars().next() {
+                *counts.entry(first).or_insert(0) += 1;
+            }
+        }
+        counts
+    }
+
+    /// Iterate over the words of this BBOW in reverse
+    /// alphabetical order: the reverse of
+    /// [`words`](Self::words).
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::new().extend_from_text("b c a");
+    /// assert_eq!(vec!["c", "b", "a"], bbow.words_rev().collect::<Vec<_>>());
+    /// ```
+    pub fn words_rev(&self) -> impl DoubleEndedIterator<Item = &str> + '_ {
+        self.0.keys().rev().map(|w| w.as_ref())
+    }
+
+    /// Report every word in this BBOW with its count as
+    /// owned `(word, count)` pairs, sorted as for
+    /// [`most_common`](Self::most_common): by descending
+    /// count, then alphabetically. Unlike `most_common` the
+    /// result does not borrow the BBOW.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::new().extend_from_text("b a b");
+    /// assert_eq!(
+    ///     vec![("b".to_string(), 2), ("a".to_string(), 1)],
+    ///     bbow.to_sorted_vec()
+    /// );
+    /// ```
+    pub fn to_sorted_vec(&self) -> Vec<(String, usize)> {
+        let mut pairs: Vec<(String, usize)> = self
+            .0
+            .iter()
+            .map(|(word, &count)| (word.to_string(), count))
+            .collect();
+        // The pairs are already in alphabetical order, and
+        // the sort is stable.
+        pairs.sort_by(|(_, a), (_, b)| b.cmp(a));
+        pairs
+    }
+
+    /// Map each word in this BBOW to its relative frequency,
+    /// as given by [`probability`](Self::probability): its
+    /// count divided by the overall number of words. The
+    /// frequencies of a non-empty BBOW sum to 1.0, and an
+    /// empty BBOW gives an empty map.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::new().extend_from_text("the cat and the hat");
+    /// let distribution = bbow.to_distribution();
+    /// assert_eq!(0.4, distribution["the"]);
+    /// assert_eq!(0.2, distribution["cat"]);
+    /// ```
+    pub fn to_distribution(&self) -> BTreeMap<&str, f64> {
+        let total = self.count() as f64;
+        self.0
+            .iter()
+            .map(|(word, &count)| (word.as_ref(), count as f64 / total))
+            .collect()
+    }
+
+    /// Iterate over the words of this BBOW with their
+    /// counts, sorted as for
+    /// [`most_common`](Self::most_common): by descending
+    /// count, then alphabetically. The order is computed
+    /// once, when this is called, but the pairs are
+    /// produced lazily, so taking the first few is cheap.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::new().extend_from_text("the cat and the hat");
+    /// let mut words = bbow.iter_by_count();
+    /// assert_eq!(Some(("the", 2)), words.next());
+    /// assert_eq!(Some(("and", 1)), words.next());
+    /// ```
+    pub fn iter_by_count(&self) -> impl Iterator<Item = (&str, usize)> {
+        let mut pairs: Vec<(&str, usize)> = self
+            .0
+            .iter()
+            .map(|(word, &count)| (word.as_ref(), count))
+            .collect();
+        // The pairs are already in alphabetical order, and
+        // the sort is stable.
+        pairs.sort_by(|(_, a), (_, b)| b.cmp(a));
+        pairs.into_iter()
+    }
+
+    /// Add a sequence of word candidates split by some
+    /// other tokenizer to this BBOW in place. Each item is
+    /// treated as a single word and normalized as in
+    /// [`extend_from_text`](Self::extend_from_text); items
+    /// that are not valid words are skipped. Borrowed items
+    /// are stored without copying where possible, as for
+    /// the `Extend<&str>` implementation.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let mut bbow = Bbow::new();
+    /// bbow.extend_from_words(vec![String::from("Cat"), String::from("42")]);
+    /// bbow.extend_from_words(["cat", "hat"]);
+    /// assert_eq!(2, bbow.match_count("cat"));
+    /// assert_eq!(3, bbow.count());
+    /// ```
+    pub fn extend_from_words<I, S>(&mut self, words: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<Cow<'a, str>>,
+    {
+        for word in words {
+            match word.into() {
+                Cow::Borrowed(word) => self.add_token(word),
+                Cow::Owned(word) => self.add_owned_token(&word),
+            }
+        }
+    }
+
+    /// Estimate the number of bytes of memory used by this
+    /// BBOW, including its map and the text of the words it
+    /// owns. Borrowed words only count the size of the
+    /// reference, since their text belongs to the borrowed
+    /// document. This is an estimate, not an exact figure:
+    /// it assumes the nodes of the underlying `BTreeMap` are
+    /// on average two thirds full, and ignores allocator
+    /// overhead.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let text = "the cat and the hat";
+    /// let borrowed = Bbow::new().extend_from_text(text);
+    /// let owned = borrowed.clone().into_owned();
+    /// assert!(owned.memory_usage() > borrowed.memory_usage());
+    /// ```
+    pub fn memory_usage(&self) -> usize {
+        let entry = std::mem::size_of::<Cow<str>>() + std::mem::size_of::<usize>();
+        let entries = self.0.len() * entry * 3 / 2;
+        let owned: usize = self
+            .0
+            .keys()
+            .map(|word| match word {
+                Cow::Borrowed(_) => 0,
+                Cow::Owned(word) => word.capacity(),
+            })
+            .sum();
+        std::mem::size_of::<Self>() + entries + owned
+    }
+
+    /// Copy the words of this BBOW and their counts into a
+    /// new map with owned keys, which can be changed without
+    /// affecting this BBOW and outlive the text it borrows.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::new().extend_from_text("b a b");
+    /// let mut frequencies = bbow.frequencies();
+    /// assert_eq!(Some(&2), frequencies.get("b"));
+    /// frequencies.clear();
+    /// assert_eq!(2, bbow.len());
+    /// ```
+    pub fn frequencies(&self) -> BTreeMap<String, usize> {
+        self.0
+            .iter()
+            .map(|(word, &count)| (word.to_string(), count))
+            .collect()
+    }
+
+    /// The map from each word of this BBOW to its count,
+    /// for algorithms this crate does not provide. The keys
+    /// are words already in the normalized form the BBOW
+    /// stores, so they should be compared with other stored
+    /// words or normalized keywords, not raw text. Every
+    /// count is at least 1.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::new().extend_from_text("The cat and the hat");
+    /// let map = bbow.as_map();
+    /// assert_eq!(Some(&2), map.get("the"));
+    /// assert_eq!(None, map.get("The"));
+    /// ```
+    pub fn as_map(&self) -> &BTreeMap<Cow<'a, str>, usize> {
+        &self.0
+    }
+
+    /// Report the count at quantile `q` of the counts of the
+    /// distinct words in this BBOW, each word counting once
+    /// however often it occurs. `q` is clamped to the range
+    /// 0.0 to 1.0, so 0.0 gives the smallest count, 0.5 the
+    /// median and 1.0 the largest. The nearest-rank method
+    /// is used: the result is the smallest count such that
+    /// at least a fraction `q` of the words have that count
+    /// or less, so it is always one of the counts. An empty
+    /// BBOW gives 0.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::new().extend_from_text("a b b c c c");
+    /// assert_eq!(2, bbow.count_quantile(0.5));
+    /// assert_eq!(3, bbow.count_quantile(1.0));
+    /// ```
+    pub fn count_quantile(&self, q: f64) -> usize {
+        let mut counts: Vec<usize> = self.0.values().copied().collect();
+        if counts.is_empty() {
+            return 0;
+        }
+        counts.sort_unstable();
+        let rank = (q.clamp(0.0, 1.0) * counts.len() as f64).ceil() as usize;
+        counts[rank.clamp(1, counts.len()) - 1]
+    }
+
+    /// Remove every word occurring fewer than `min_count`
+    /// times from this BBOW, returning the number of
+    /// distinct words removed.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let mut bbow = Bbow::new().extend_from_text("the cat and the hat");
+    /// assert_eq!(3, bbow.prune_below(2));
+    /// assert_eq!(vec!["the"], bbow.words().collect::<Vec<_>>());
+    /// ```
+    pub fn prune_below(&mut self, min_count: usize) -> usize {
+        let before = self.len();
+        self.retain(|_, count| count >= min_count);
+        before - self.len()
+    }
+
+    /// Count the distinct words occurring at least
+    /// `min_count` times in this BBOW: the words
+    /// [`prune_below`](Self::prune_below) would keep.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::new().extend_from_text("the cat and the hat");
+    /// assert_eq!(1, bbow.count_words_above(2));
+    /// assert_eq!(4, bbow.count_words_above(1));
+    /// ```
+    pub fn count_words_above(&self, min_count: usize) -> usize {
+        self.0.values().filter(|&&count| count >= min_count).count()
+    }
+
+    /// Reduce the count of every word occurring more than
+    /// `max` times to `max`, leaving smaller counts alone.
+    /// Clamping to 0 empties the BBOW, since a BBOW never
+    /// stores zero counts.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let mut bbow = Bbow::new().extend_from_text("the the the cat");
+    /// bbow.clamp_counts(2);
+    /// assert_eq!(2, bbow.match_count("the"));
+    /// assert_eq!(1, bbow.match_count("cat"));
+    /// ```
+    pub fn clamp_counts(&mut self, max: usize) {
+        if max == 0 {
+            self.0.clear();
+        }
+        for count in self.0.values_mut() {
+            *count = (*count).min(max);
+        }
+    }
+}
+
+impl Bbow<'static> {
+    /// Make a new BBOW from the text of the file at `path`,
+    /// following the same rules as
+    /// [`extend_from_text`](Self::extend_from_text).
+    ///
+    /// # Errors
+    ///
+    /// Any error opening or reading the file is returned.
+    /// A file that is not valid UTF-8 gives an error of kind
+    /// [`io::ErrorKind::InvalidData`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::from_file("document.txt")?;
+    /// println!("{} unique words", bbow.len());
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn from_file<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let mut bbow = Bbow::new();
+        bbow.extend_from_reader(BufReader::new(File::open(path)?))?;
+        Ok(bbow)
+    }
+
+    /// Make a BBOW with the given `config` from words that
+    /// are already in the form such a BBOW stores, as saved
+    /// from one, keeping every word and count exactly. An
+    /// error message is returned for the first word that is
+    /// not in stored form, is repeated, or has a count of
+    /// zero.
+    #[cfg(any(feature = "serde", feature = "gzip"))]
+    fn from_stored_words<I: IntoIterator<Item = (String, usize)>>(
+        config: BbowConfig,
+        words: I,
+    ) -> Result<Self, String> {
+        let mut map = BTreeMap::new();
+        for (word, count) in words {
+            if normalize(&word, &config).as_deref() != Some(word.as_str()) {
+                return Err(format!("invalid word {word:?}"));
+            }
+            if count == 0 {
+                return Err(format!("zero count for word {word:?}"));
+            }
+            match map.entry(Cow::Owned(word)) {
+                btree_map::Entry::Vacant(entry) => {
+                    entry.insert(count);
+                }
+                btree_map::Entry::Occupied(entry) => {
+                    return Err(format!("repeated word {:?}", entry.key()));
+                }
+            }
+        }
+        Ok(Bbow(map, config))
+    }
+}
+
+/// Two BBOWs are equal when they hold the same words with
+/// the same counts. Their configurations are not compared.
+///
+/// # Examples:
+///
+/// ```
+/// # use bbow::Bbow;
+/// let a = Bbow::new().extend_from_text("the cat and the hat");
+/// let b = Bbow::new().extend_from_text("Hat, the and THE cat.");
+/// assert_eq!(a, b);
+/// ```
+impl PartialEq for Bbow<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl Eq for Bbow<'_> {}
+
+/// Hashing is consistent with equality: only the words and
+/// their counts are hashed, in sorted word order.
+impl Hash for Bbow<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}
+
+/// Summarize a BBOW: the overall number of words, the
+/// number of unique words, and the most frequent words with
+/// their counts, as given by [`Bbow::most_common`]. Ten
+/// words are shown unless a precision is given, which sets
+/// the number of words instead. A width pads the whole
+/// summary, aligned to the left unless asked otherwise.
+///
+/// # Examples:
+///
+/// ```
+/// # use bbow::Bbow;
+/// let bbow = Bbow::new().extend_from_text("the cat and the hat");
+/// assert_eq!(
+///     "5 words, 4 unique: the (2), and (1), cat (1), hat (1)",
+///     bbow.to_string()
+/// );
+/// assert_eq!("5 words, 4 unique: the (2)", format!("{bbow:.1}"));
+/// assert_eq!("[  5 words, 4 unique]", format!("[{bbow:>19.0}]"));
+/// ```
+impl fmt::Display for Bbow<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use fmt::Write as _;
+
+        let mut summary = format!("{} words, {} unique", self.count(), self.len());
+        for (i, (word, count)) in self
+            .most_common(f.precision().unwrap_or(10))
+            .into_iter()
+            .enumerate()
+        {
+            let separator = if i == 0 { ":" } else { "," };
+            write!(summary, "{separator} {word} ({count})")?;
+        }
+        // The precision is used up choosing the words, so
+        // `f.pad` would truncate the summary: pad it by hand.
+        let padding = f
+            .width()
+            .unwrap_or(0)
+            .saturating_sub(summary.chars().count());
+        let (before, after) = match f.align() {
+            Some(fmt::Alignment::Right) => (padding, 0),
+            Some(fmt::Alignment::Center) => (padding / 2, padding - padding / 2),
+            _ => (0, padding),
+        };
+        let fill = f.fill();
+        for _ in 0..before {
+            f.write_char(fill)?;
+        }
+        f.write_str(&summary)?;
+        for _ in 0..after {
+            f.write_char(fill)?;
+        }
+        Ok(())
+    }
+}
+
+/// Combine two BBOWs, summing the counts of shared words.
+///
+/// # Examples:
+///
+/// ```
+/// # use bbow::Bbow;
+/// let a = Bbow::new().extend_from_text("the cat");
+/// let b = Bbow::new().extend_from_text("the hat");
+/// let combined = a + b;
+/// assert_eq!(2, combined.match_count("the"));
+/// ```
+impl<'a> Add<Bbow<'a>> for Bbow<'a> {
+    type Output = Bbow<'a>;
+
+    fn add(mut self, rhs: Bbow<'a>) -> Self::Output {
+        self.merge(rhs);
+        self
+    }
+}
+
+/// Add a BBOW into this one in place, summing the counts
+/// of shared words.
+impl<'a> AddAssign<Bbow<'a>> for Bbow<'a> {
+    fn add_assign(&mut self, rhs: Bbow<'a>) {
+        self.merge(rhs);
+    }
+}
+
+/// Multiply every count in a BBOW by `factor`, as by
+/// [`Bbow::scale`].
+///
+/// # Examples:
+///
+/// ```
+/// # use bbow::Bbow;
+/// let weighted = Bbow::new().extend_from_text("the cat") * 3;
+/// assert_eq!(3, weighted.match_count("cat"));
+/// ```
+impl<'a> Mul<usize> for Bbow<'a> {
+    type Output = Bbow<'a>;
+
+    fn mul(mut self, factor: usize) -> Self::Output {
+        self.scale(factor);
+        self
+    }
+}
+
+/// The multiset sum of two borrowed BBOWs, as by
+/// [`Bbow::union`].
+///
+/// # Examples:
+///
+/// ```
+/// # use bbow::Bbow;
+/// let a = Bbow::new().extend_from_text("the cat");
+/// let b = Bbow::new().extend_from_text("the hat");
+/// let all = &a | &b;
+/// assert_eq!(2, all.match_count("the"));
+/// assert_eq!(1, a.match_count("the"));
+/// ```
+impl<'a> BitOr<&Bbow<'a>> for &Bbow<'a> {
+    type Output = Bbow<'a>;
+
+    fn bitor(self, rhs: &Bbow<'a>) -> Self::Output {
+        self.union(rhs)
+    }
+}
+
+/// The multiset intersection of two borrowed BBOWs, as by
+/// [`Bbow::intersection`].
+///
+/// # Examples:
+///
+/// ```
+/// # use bbow::Bbow;
+/// let a = Bbow::new().extend_from_text("the the cat");
+/// let b = Bbow::new().extend_from_text("the hat");
+/// let common = &a & &b;
+/// assert_eq!(1, common.match_count("the"));
+/// assert_eq!(1, common.len());
+/// ```
+impl<'a> BitAnd<&Bbow<'a>> for &Bbow<'a> {
+    type Output = Bbow<'a>;
+
+    fn bitand(self, rhs: &Bbow<'a>) -> Self::Output {
+        self.intersection(rhs)
+    }
+}
+
+/// Iterate over the valid words in `text`, normalized
+/// according to `config`, each with the byte range of the
+/// trimmed word in `text`.
+fn word_spans<'t>(
+    text: &'t str,
+    config: BbowConfig,
+) -> impl Iterator<Item = (Cow<'t, str>, Range<usize>)> + 't {
+    tokens(text).filter_map(move |token| {
+        let word = normalize(token, &config)?;
+        // Tokens are slices of text, so their offset can be
+        // recovered from their address.
+        let offset = token.as_ptr() as usize - text.as_ptr() as usize;
+        let trimmed = token.trim_start_matches(|c: char| !config.is_letter(c));
+        let start = offset + token.len() - trimmed.len();
+        Some((word, start..start + letters_end(trimmed, &config)))
+    })
+}
+
+/// The length of `text` up to the end of its last letter,
+/// including any combining marks straight after it, which
+/// are part of the same written character.
+fn letters_end(text: &str, config: &BbowConfig) -> usize {
+    let mut end = 0;
+    for (i, c) in text.char_indices() {
+        if config.is_letter(c) || (end == i && end > 0 && is_mark(c)) {
+            end = i + c.len_utf8();
+        }
+    }
+    end
+}
+
+/// Is `c` a combining mark, which composes with the
+/// character before it?
+#[cfg(feature = "unicode-normalization")]
+fn is_mark(c: char) -> bool {
+    is_combining_mark(c)
+}
+
+/// Without the `unicode-normalization` feature combining
+/// marks are not composed, so are treated as punctuation.
+#[cfg(not(feature = "unicode-normalization"))]
+fn is_mark(_c: char) -> bool {
+    false
+}
+
+/// Split `text` into its valid words, as in
+/// [`Bbow::extend_from_text`], giving each normalized word
+/// with the byte range in `text` it came from. The range
+/// covers the word after leading and trailing punctuation
+/// is trimmed, along with any combining marks on its last
+/// letter, so slicing `text` with it gives the word as
+/// written.
+///
+/// # Examples:
+///
+/// ```
+/// # use bbow::tokenize_with_spans;
+/// let text = "Hello, (world)!";
+/// let tokens = tokenize_with_spans(text);
+/// assert_eq!("hello", tokens[0].0);
+/// assert_eq!("Hello", &text[tokens[0].1.clone()]);
+/// assert_eq!(8..13, tokens[1].1);
+/// ```
+pub fn tokenize_with_spans(text: &str) -> Vec<(Cow<'_, str>, Range<usize>)> {
+    word_spans(text, BbowConfig::default()).collect()
+}
+
+/// Find each occurrence of `keyword` in `text` and return
+/// a snippet of the text around it, extending `window`
+/// characters either side of the word, in order of
+/// appearance. Words are found and normalized as in
+/// [`Bbow::extend_from_text`], and so is the keyword, so
+/// only whole words match.
+///
+/// # Examples:
+///
+/// ```
+/// # use bbow::concordance;
+/// let text = "The cat sat. A category, and a CAT.";
+/// assert_eq!(vec!["he cat sa", " a CAT."], concordance(text, "cat", 3));
+/// ```
+pub fn concordance<'t>(text: &'t str, keyword: &str, window: usize) -> Vec<&'t str> {
+    let config = BbowConfig::default();
+    let Some(keyword) = normalize(keyword, &config) else {
+        return Vec::new();
+    };
+    word_spans(text, config)
+        .filter(|(word, _)| *word == keyword)
+        .map(|(_, Range { start, end })| {
+            let from = text[..start]
+                .char_indices()
+                .rev()
+                .take(window)
+                .last()
+                .map_or(start, |(i, _)| i);
+            let to = end
+                + text[end..]
+                    .chars()
+                    .take(window)
+                    .map(char::len_utf8)
+                    .sum::<usize>();
+            &text[from..to]
+        })
+        .collect()
+}
+
+/// Count the bigrams in `text`: the pairs of valid words
+/// that appear next to each other. Words are found and
+/// normalized exactly as in [`Bbow::extend_from_text`].
+/// Tokens that are not valid words are skipped, so the
+/// words on either side of them are still adjacent.
+///
+/// Bigrams span sentence boundaries: in `"Hi. Bye."`,
+/// `("hi", "bye")` is a bigram. Split the text with
+/// [`sentences`] first to keep them apart.
+///
+/// # Examples:
+///
+/// ```
+/// # use bbow::bigrams;
+/// let pairs = bigrams("The cat sat.");
+/// assert_eq!(2, pairs.len());
+/// assert_eq!(Some(&1), pairs.get(&("the".into(), "cat".into())));
+/// assert_eq!(Some(&1), pairs.get(&("cat".into(), "sat".into())));
+/// ```
+pub fn bigrams(text: &str) -> BTreeMap<(Cow<'_, str>, Cow<'_, str>), usize> {
+    let config = BbowConfig::default();
+    let mut words = tokens(text).filter_map(|token| normalize(token, &config));
+    let mut pairs = BTreeMap::new();
+    if let Some(mut previous) = words.next() {
+        for word in words {
+            *pairs.entry((previous, word.clone())).or_insert(0) += 1;
+            previous = word;
+        }
+    }
+    pairs
+}
+
+/// Does `token` end a sentence? It does if the
+/// punctuation at its end includes `.`, `!` or `?`.
+fn ends_sentence(token: &str) -> bool {
+    let word_end = token.trim_end_matches(|c: char| !c.is_alphanumeric()).len();
+    token[word_end..].contains(['.', '!', '?'])
+}
+
+/// Split `text` into sentences, giving an iterator over the
+/// valid words of each, found and normalized exactly as in
+/// [`Bbow::extend_from_text`]. A sentence ends at a token
+/// whose trailing punctuation includes `.`, `!` or `?`, so
+/// `"Hi!"` and `"(done.)"` end sentences but `"3.14"` does
+/// not. Abbreviations like `"Mr."` end sentences too.
+/// Sentences without any valid words are skipped.
+///
+/// # Examples:
+///
+/// ```
+/// # use bbow::sentences;
+/// let words: Vec<Vec<_>> = sentences("Hi there. Bye now!")
+///     .map(|sentence| sentence.collect())
+///     .collect();
+/// assert_eq!(vec![vec!["hi", "there"], vec!["bye", "now"]], words);
+/// ```
+pub fn sentences(text: &str) -> impl Iterator<Item = impl Iterator<Item = Cow<'_, str>>> {
+    let config = BbowConfig::default();
+    let mut rest = text.strip_prefix('\u{FEFF}').unwrap_or(text);
+    std::iter::from_fn(move || loop {
+        if rest.is_empty() {
+            return None;
+        }
+        // Tokens are slices of rest, so their offset can be
+        // recovered from their address.
+        let end = tokens(rest)
+            .find(|token| ends_sentence(token))
+            .map_or(rest.len(), |token| {
+                token.as_ptr() as usize - rest.as_ptr() as usize + token.len()
+            });
+        let sentence = &rest[..end];
+        rest = &rest[end..];
+        if tokens(sentence).any(|token| normalize(token, &config).is_some()) {
+            return Some(tokens(sentence).filter_map(move |token| normalize(token, &config)));
+        }
+    })
+}
+
+/// Count how often each pair of valid words in `text`
+/// occurs within `window` words of each other. Words are
+/// found and normalized exactly as in [`bigrams`], so a
+/// `window` of 1 counts adjacent words. Each pair is stored
+/// in alphabetical order, so that `"cat sat"` and
+/// `"sat cat"` both count the pair `("cat", "sat")`. A word
+/// repeated within the window pairs with itself.
+///
+/// # Examples:
+///
+/// ```
+/// # use bbow::cooccurrences;
+/// let pairs = cooccurrences("the cat saw the dog", 2);
+/// assert_eq!(Some(&2), pairs.get(&("cat".into(), "the".into())));
+/// assert_eq!(Some(&1), pairs.get(&("dog".into(), "saw".into())));
+/// assert_eq!(None, pairs.get(&("cat".into(), "dog".into())));
+/// ```
+pub fn cooccurrences(text: &str, window: usize) -> BTreeMap<(String, String), usize> {
+    let config = BbowConfig::default();
+    let words: Vec<Cow<str>> = tokens(text)
+        .filter_map(|token| normalize(token, &config))
+        .collect();
+    let mut pairs = BTreeMap::new();
+    for (i, word) in words.iter().enumerate() {
+        for other in words.iter().skip(i + 1).take(window) {
+            let pair = if word <= other {
+                (word.to_string(), other.to_string())
+            } else {
+                (other.to_string(), word.to_string())
+            };
+            *pairs.entry(pair).or_insert(0) += 1;
+        }
+    }
+    pairs
+}
+
+/// Build a BBOW from a sequence of word candidates. Each
+/// item is treated as a single word and normalized as in
+/// [`Bbow::extend_from_text`]; items that are not valid
+/// words are skipped.
+///
+/// # Examples:
+///
+/// ```
+/// # use bbow::Bbow;
+/// let bbow: Bbow = ["The", "cat", "the", "3"].into_iter().collect();
+/// assert_eq!(2, bbow.match_count("the"));
+/// assert_eq!(3, bbow.count());
+/// ```
+impl<'a> FromIterator<&'a str> for Bbow<'a> {
+    fn from_iter<I: IntoIterator<Item = &'a str>>(iter: I) -> Self {
+        let mut bbow = Bbow::new();
+        bbow.extend(iter);
+        bbow
+    }
+}
+
+/// Add a sequence of word candidates to a BBOW, summing
+/// with the counts already present. Each item is treated
+/// as a single word and normalized as in
+/// [`Bbow::extend_from_text`]; items that are not valid
+/// words are skipped.
+///
+/// # Examples:
+///
+/// ```
+/// # use bbow::Bbow;
+/// let mut bbow = Bbow::new().extend_from_text("the cat");
+/// bbow.extend("The hat".split(' '));
+/// assert_eq!(2, bbow.match_count("the"));
+/// ```
+impl<'a> Extend<&'a str> for Bbow<'a> {
+    fn extend<I: IntoIterator<Item = &'a str>>(&mut self, iter: I) {
+        for token in iter {
+            self.add_token(token);
+        }
+    }
+}
+
+/// Add a sequence of `(word, count)` pairs to a BBOW, such
+/// as those from a BBOW's owned iterator, summing with the
+/// counts already present. Each word is normalized as in
+/// [`Bbow::extend_from_text`]; pairs whose word is not
+/// valid, or whose count is zero, are skipped.
+///
+/// # Examples:
+///
+/// ```
+/// # use bbow::Bbow;
+/// let mut bbow = Bbow::new().extend_from_text("the cat");
+/// bbow.extend([("The".into(), 2), ("hat".into(), 1)]);
+/// assert_eq!(3, bbow.match_count("the"));
+/// assert_eq!(5, bbow.count());
+/// ```
+impl<'a> Extend<(Cow<'a, str>, usize)> for Bbow<'a> {
+    fn extend<I: IntoIterator<Item = (Cow<'a, str>, usize)>>(&mut self, iter: I) {
+        for (word, count) in iter {
+            if count == 0 {
+                continue;
+            }
+            let word = match word {
+                Cow::Borrowed(word) => normalize(word, &self.1),
+                Cow::Owned(word) => {
+                    normalize(&word, &self.1).map(|word| Cow::Owned(word.into_owned()))
+                }
+            };
+            if let Some(word) = word {
+                *self.0.entry(word).or_insert(0) += count;
+            }
+        }
+    }
+}
+
+/// Iterator over the `(word, count)` pairs of a BBOW, in
+/// sorted word order. Created by iterating over a `&Bbow`.
+#[derive(Debug, Clone)]
+pub struct Iter<'a>(btree_map::Iter<'a, Cow<'a, str>, usize>);
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = (&'a str, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(word, &count)| (word.as_ref(), count))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for Iter<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0
+            .next_back()
+            .map(|(word, &count)| (word.as_ref(), count))
+    }
+}
+
+impl ExactSizeIterator for Iter<'_> {}
+
+/// Iterate over the `(word, count)` pairs of a BBOW, in
+/// sorted word order.
+///
+/// # Examples:
+///
+/// ```
+/// # use bbow::Bbow;
+/// let bbow = Bbow::new().extend_from_text("the cat and the hat");
+/// let mut total = 0;
+/// for (word, count) in &bbow {
+///     assert_eq!(bbow.match_count(word), count);
+///     total += count;
+/// }
+/// assert_eq!(bbow.count(), total);
+/// ```
+impl<'a> IntoIterator for &'a Bbow<'a> {
+    type Item = (&'a str, usize);
+    type IntoIter = Iter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        Iter(self.0.iter())
+    }
+}
+
+/// Consume a BBOW, iterating over its owned
+/// `(word, count)` pairs in sorted word order. Words
+/// borrowed from the text stay borrowed, so nothing is
+/// copied.
+///
+/// # Examples:
+///
+/// ```
+/// # use bbow::Bbow;
+/// let bbow = Bbow::new().extend_from_text("the cat and the hat");
+/// let mut words = Vec::new();
+/// for (word, count) in bbow {
+///     words.push((word.into_owned(), count));
+/// }
+/// assert_eq!(("the".to_string(), 2), words[3]);
+/// ```
+impl<'a> IntoIterator for Bbow<'a> {
+    type Item = (Cow<'a, str>, usize);
+    type IntoIter = btree_map::IntoIter<Cow<'a, str>, usize>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_is_empty() {
+        let bbow = Bbow::new();
+        assert!(bbow.is_empty());
+        assert_eq!(bbow.len(), 0);
+        assert_eq!(bbow.count(), 0);
+    }
+
+    // Test from documentation example
+    #[test]
+    fn test_b_banana() {
+        let bbow = Bbow::new().extend_from_text("b b b-banana b");
+        assert_eq!(0, bbow.match_count("b-banana")); // Should be zero since it has a hyphen
+        assert_eq!(3, bbow.match_count("b"));
+    }
+
+    // Test from documentation example
+    #[test]
+    fn test_cant_stop() {
+        let bbow = Bbow::new().extend_from_text("Can't stop this! Stop!");
+        assert_eq!(2, bbow.len());
+        assert_eq!(3, bbow.count());
+    }
+
+    #[test]
+    fn test_case_sensitivity() {
+        let bbow = Bbow::new().extend_from_text("TEST test TEst tESt");
+        assert_eq!(4, bbow.match_count("test"));
+        assert_eq!(1, bbow.len());
+        assert_eq!(4, bbow.count());
+    }
+
+    #[test]
     fn test_invalid_keyword_param() {
         let bbow = Bbow::new().extend_from_text("Hello world.");
-        assert_eq!(0, bbow.match_count("")); // empty string
-        assert_eq!(0, bbow.match_count("hello.")); // with punctuation
-        assert_eq!(0, bbow.match_count("Hello")); // with uppercase
-        assert_eq!(0, bbow.match_count("hello world")); // multiple words
+        assert_eq!(0, bbow.match_count("")); // empty string
+        assert_eq!(0, bbow.match_count("hello.")); // with punctuation
+        assert_eq!(0, bbow.match_count("Hello")); // with uppercase
+        assert_eq!(0, bbow.match_count("hello world")); // multiple words
+    }
+
+    #[test]
+    fn test_large_iterative_input() {
+        let mut bbow = Bbow::new().extend_from_text("Lets iterate over this text.");
+
+        // Verify initial count
+        assert_eq!(5, bbow.count());
+
+        //
+        for i in 0..100 {
+            let text = match i % 2 {
+                0 => "Lets iterate over this text.",
+                _ => "Lets iterate over that text.",
+            };
+            bbow = bbow.extend_from_text(text);
+        }
+
+        // Unique count should be: 5 + 1 = 6
+        assert_eq!(6, bbow.len());
+        // Total bag of words should be: 5 + (5 * 50) + (5 * 50) = 505
+        assert_eq!(505, bbow.count());
+
+        // "This" count should be 51
+        assert_eq!(51, bbow.match_count("this"));
+        // "That" count should be 50
+        assert_eq!(50, bbow.match_count("that"));
+        // "iterate" count should be 101
+        assert_eq!(101, bbow.match_count("iterate"));
+        //
+    }
+
+    #[test]
+    fn test_add_text_in_place() {
+        let mut bbow = Bbow::new();
+        for i in 0..100 {
+            let text = match i % 2 {
+                0 => "Lets iterate over this text.",
+                _ => "Lets iterate over that text.",
+            };
+            bbow.add_text(text);
+        }
+        assert_eq!(6, bbow.len());
+        assert_eq!(500, bbow.count());
+        assert_eq!(50, bbow.match_count("this"));
+
+        // Same tokenization rules as extend_from_text
+        let built = Bbow::new().extend_from_text("Can't stop this! Stop!");
+        let mut added = Bbow::new();
+        added.add_text("Can't stop this! Stop!");
+        assert_eq!(built.len(), added.len());
+        assert_eq!(built.count(), added.count());
+        assert_eq!(built.match_count("stop"), added.match_count("stop"));
+    }
+
+    #[test]
+    fn test_extend_from_texts() {
+        let texts = vec!["The cat sat.", "", "THE HAT!", "café, the end"];
+        let mut sequential = Bbow::new();
+        for text in &texts {
+            sequential = sequential.extend_from_text(text);
+        }
+        let bbow = Bbow::new().extend_from_texts(texts);
+        assert_eq!(sequential, bbow);
+        assert_eq!(3, bbow.match_count("the"));
+
+        let bbow = bbow.extend_from_texts(["the"]);
+        assert_eq!(4, bbow.match_count("the"));
+        assert!(Bbow::new().extend_from_texts([]).is_empty());
+    }
+
+    #[test]
+    fn test_words_borrows_bag_only() {
+        fn first_word<'b>(bbow: &'b Bbow<'static>) -> Option<&'b str> {
+            bbow.words().next()
+        }
+        assert_eq!(
+            Some("cat"),
+            first_word(&Bbow::new().extend_from_text("the cat"))
+        );
+
+        // The other word views borrow only the bag too.
+        fn views<'b>(bbow: &'b Bbow<'static>) -> Vec<&'b str> {
+            let mut views = Vec::new();
+            views.extend(bbow.words_with_prefix("c").map(|(word, _)| word));
+            views.extend(bbow.words_with_count(2));
+            views.extend(bbow.words_rev());
+            views.extend(bbow.iter_by_count().map(|(word, _)| word));
+            views.extend(bbow.group_by_first_char().into_values().flatten());
+            views
+        }
+        let bbow = Bbow::new().extend_from_text("the cat the");
+        assert_eq!(
+            vec!["cat", "the", "the", "cat", "the", "cat", "cat", "the"],
+            views(&bbow)
+        );
+
+        let text = String::from("the cat");
+        let mut bbow = Bbow::new().extend_from_text(&text);
+        {
+            let words: Vec<&str> = bbow.words().collect();
+            assert_eq!(vec!["cat", "the"], words);
+        }
+        {
+            let pairs: Vec<(&str, usize)> = bbow.iter_by_count().collect();
+            assert_eq!(vec![("cat", 1), ("the", 1)], pairs);
+            assert_eq!(Some("the"), bbow.words_rev().next());
+        }
+        // The borrows taken by words() and the other views
+        // have ended, though the text is still borrowed.
+        bbow.add_text("a hat");
+        assert_eq!(4, bbow.words().count());
+        assert_eq!("the cat", text);
+    }
+
+    #[test]
+    fn test_diff_unicode_types() {
+        let mut bbow = Bbow::new().extend_from_text("café café café cafe!");
+
+        assert_eq!(2, bbow.len());
+        assert_eq!(4, bbow.count());
+        assert_eq!(3, bbow.match_count("café"));
+        assert_eq!(1, bbow.match_count("cafe"));
+
+        // Japanese characters
+        // Source: google translate
+        bbow = bbow.extend_from_text("日本語");
+        assert_eq!(3, bbow.len());
+        assert_eq!(5, bbow.count());
+        assert_eq!(1, bbow.match_count("日本語"));
+
+        // Arabic characters
+        // Source: google translate
+        bbow = bbow.extend_from_text("Hello in arabic: مرحبًا");
+        assert_eq!(7, bbow.len());
+        assert_eq!(9, bbow.count());
+        assert_eq!(1, bbow.match_count("مرحبًا"));
+    }
+
+    #[test]
+    fn test_most_common() {
+        let bbow = Bbow::new().extend_from_text("b a c b a b d");
+        assert_eq!(vec![("b", 3), ("a", 2)], bbow.most_common(2));
+        // n larger than len() returns everything
+        assert_eq!(
+            vec![("b", 3), ("a", 2), ("c", 1), ("d", 1)],
+            bbow.most_common(10)
+        );
+        assert!(bbow.most_common(0).is_empty());
+        assert!(Bbow::new().most_common(3).is_empty());
+    }
+
+    #[test]
+    fn test_most_common_ties_alphabetical() {
+        let bbow = Bbow::new().extend_from_text("zeta alpha mu zeta alpha mu beta");
+        // Three-way tie at 2: alphabetical order decides, and
+        // the cut at n = 2 drops the alphabetically last one.
+        assert_eq!(vec![("alpha", 2), ("mu", 2)], bbow.most_common(2));
+        assert_eq!(
+            vec![("alpha", 2), ("mu", 2), ("zeta", 2), ("beta", 1)],
+            bbow.most_common(4)
+        );
+    }
+
+    #[test]
+    fn test_merge_sums_counts() {
+        let mut a = Bbow::new().extend_from_text("the the the cat");
+        let b = Bbow::new().extend_from_text("The the THE dog");
+        let total = a.count() + b.count();
+
+        a.merge(b);
+        assert_eq!(6, a.match_count("the"));
+        assert_eq!(1, a.match_count("cat"));
+        // Words unique to other are inserted
+        assert_eq!(1, a.match_count("dog"));
+        assert_eq!(3, a.len());
+        assert_eq!(total, a.count());
+
+        // Merging an empty bag changes nothing
+        a.merge(Bbow::new());
+        assert_eq!(total, a.count());
+    }
+
+    #[test]
+    fn test_add_operators() {
+        // Disjoint vocabularies
+        let a = Bbow::new().extend_from_text("one two two");
+        let b = Bbow::new().extend_from_text("three four");
+        let expected = a.count() + b.count();
+        let sum = a + b;
+        assert_eq!(expected, sum.count());
+        assert_eq!(4, sum.len());
+
+        // Overlapping vocabularies
+        let a = Bbow::new().extend_from_text("one two two");
+        let b = Bbow::new().extend_from_text("two three");
+        let expected = a.count() + b.count();
+        let sum = a.clone() + b.clone();
+        assert_eq!(expected, sum.count());
+        assert_eq!(3, sum.match_count("two"));
+
+        let mut acc = a;
+        acc += b;
+        assert_eq!(expected, acc.count());
+        assert_eq!(3, acc.len());
+        assert_eq!(3, acc.match_count("two"));
+    }
+
+    #[test]
+    fn test_set_operators() {
+        // Overlapping vocabularies
+        let a = Bbow::new().extend_from_text("one two two");
+        let b = Bbow::new().extend_from_text("two three");
+        let all = &a | &b;
+        assert_eq!(a.union(&b), all);
+        assert_eq!(3, all.match_count("two"));
+        assert_eq!(5, all.count());
+        let common = &a & &b;
+        assert_eq!(a.intersection(&b), common);
+        assert_eq!(vec!["two"], common.words().collect::<Vec<_>>());
+        assert_eq!(1, common.count());
+        // Both operands are still usable.
+        assert_eq!(3, a.count());
+        assert_eq!(2, b.count());
+
+        // Disjoint vocabularies
+        let c = Bbow::new().extend_from_text("four five");
+        assert_eq!(4, (&a | &c).len());
+        assert!((&a & &c).is_empty());
+    }
+
+    #[test]
+    fn test_contains() {
+        let bbow = Bbow::new().extend_from_text("Hello world.");
+        assert!(bbow.contains("hello"));
+        assert!(bbow.contains("world"));
+        assert!(!bbow.contains("missing"));
+        assert!(!bbow.contains("")); // empty string
+        assert!(!bbow.contains("hello.")); // with punctuation
+        assert!(!bbow.contains("Hello")); // with uppercase
+    }
+
+    #[test]
+    fn test_contains_all_any() {
+        let bbow = Bbow::new().extend_from_text("The cat and the hat");
+        assert!(bbow.contains_all(["the", "Cat", "(hat)"]));
+        assert!(!bbow.contains_all(["the", "dog"]));
+        assert!(bbow.contains_any(["dog", "HAT"]));
+        assert!(!bbow.contains_any(["dog", "bird"]));
+
+        // "42!" is not a valid word once normalized.
+        assert!(!bbow.contains_all(["cat", "42!"]));
+        assert!(bbow.contains_any(["42!", "cat"]));
+        assert!(!bbow.contains_any(["42!"]));
+
+        let none: [&str; 0] = [];
+        assert!(bbow.contains_all(none));
+        assert!(!bbow.contains_any(none));
+        assert!(Bbow::new().contains_all(Vec::<String>::new()));
+
+        let owned = vec![String::from("The"), String::from("and")];
+        assert!(bbow.contains_all(&owned));
+    }
+
+    #[test]
+    fn test_match_count_normalized() {
+        let bbow = Bbow::new().extend_from_text("Hello world. HELLO, café!");
+        assert_eq!(2, bbow.match_count_normalized("Hello!"));
+        assert_eq!(2, bbow.match_count_normalized("\"hELLo\""));
+        assert_eq!(1, bbow.match_count_normalized("World..."));
+        assert_eq!(1, bbow.match_count_normalized("CAFÉ"));
+        assert_eq!(0, bbow.match_count_normalized("")); // empty string
+        assert_eq!(0, bbow.match_count_normalized("!?")); // only punctuation
+        assert_eq!(0, bbow.match_count_normalized("hello world")); // multiple words
+
+        // Strict lookup is unchanged
+        assert_eq!(0, bbow.match_count("Hello!"));
+    }
+
+    #[cfg(feature = "unicode-normalization")]
+    #[test]
+    fn test_nfc_nfd_unify() {
+        let nfc = "caf\u{e9}";
+        let nfd = "cafe\u{301}";
+        let text = format!("{nfc} {nfd} {nfd}!");
+        let bbow = Bbow::new().extend_from_text(&text);
+        assert_eq!(1, bbow.len());
+        assert_eq!(3, bbow.count());
+        assert_eq!(3, bbow.match_count(nfc));
+        assert_eq!(3, bbow.match_count(nfd));
+        assert!(bbow.contains(nfd));
+        assert_eq!(3, bbow.match_count_normalized("CAFE\u{301}"));
+    }
+
+    #[test]
+    fn test_into_owned() {
+        let owned: Bbow<'static> = {
+            let text = String::from("Hello hello world, HELLO again.");
+            let bbow = Bbow::new().extend_from_text(&text);
+            bbow.into_owned()
+            // text is dropped here
+        };
+        assert_eq!(3, owned.match_count("hello"));
+        assert_eq!(1, owned.match_count("world"));
+        assert_eq!(3, owned.len());
+        assert_eq!(5, owned.count());
+        assert!(owned.0.keys().all(|w| matches!(w, Cow::Owned(_))));
+    }
+
+    #[test]
+    fn test_iterate_pairs() {
+        let bbow = Bbow::new().extend_from_text("b a c b a b");
+        let pairs: Vec<(&str, usize)> = (&bbow).into_iter().collect();
+        assert_eq!(vec![("a", 2), ("b", 3), ("c", 1)], pairs);
+        assert_eq!(bbow.len(), (&bbow).into_iter().len());
+        assert_eq!(Some(("c", 1)), (&bbow).into_iter().next_back());
+    }
+
+    #[test]
+    fn test_into_iter_owned() {
+        let text = "the cat and the hat";
+        let bbow = Bbow::new().extend_from_text(text);
+        let expected = bbow.count();
+        let pairs: Vec<(Cow<str>, usize)> = bbow.into_iter().collect();
+        assert_eq!(
+            vec![("and", 1), ("cat", 1), ("hat", 1), ("the", 2)],
+            pairs
+                .iter()
+                .map(|(word, count)| (word.as_ref(), *count))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            expected,
+            pairs.iter().map(|(_, count)| count).sum::<usize>()
+        );
+        // The words were borrowed from the text, and still are.
+        assert!(pairs
+            .iter()
+            .all(|(word, _)| matches!(word, Cow::Borrowed(_))));
+
+        let mut total = 0;
+        for (word, count) in Bbow::new().extend_from_text("Hello HELLO") {
+            assert_eq!("hello", word);
+            total += count;
+        }
+        assert_eq!(2, total);
+    }
+
+    #[test]
+    fn test_collect_from_words() {
+        let words = vec![
+            "Hello",
+            "world!",
+            "hello",
+            "",
+            "...",
+            "b-banana",
+            "42",
+            "two words",
+            "(World)",
+        ];
+        let bbow: Bbow = words.into_iter().collect();
+        assert_eq!(2, bbow.len());
+        assert_eq!(4, bbow.count());
+        assert_eq!(2, bbow.match_count("hello"));
+        assert_eq!(2, bbow.match_count("world"));
+    }
+
+    #[test]
+    fn test_extend_sums_existing() {
+        let mut bbow = Bbow::new().extend_from_text("one two two");
+        bbow.extend(vec!["Two", "three", "one!", "4"]);
+        assert_eq!(2, bbow.match_count("one"));
+        assert_eq!(3, bbow.match_count("two"));
+        assert_eq!(1, bbow.match_count("three"));
+        assert_eq!(3, bbow.len());
+        assert_eq!(6, bbow.count());
+
+        // Works with iterator adapters
+        bbow.extend("TWO,two".split(',').filter(|w| !w.is_empty()));
+        assert_eq!(5, bbow.match_count("two"));
+    }
+
+    #[test]
+    fn test_extend_with_counts() {
+        let mut bbow = Bbow::new().extend_from_text("the cat and the hat");
+        let pairs: Vec<(Cow<str>, usize)> = vec![
+            ("THE".into(), 3),
+            (String::from("cat").into(), 2),
+            ("dog!".into(), 1),
+            ("bat".into(), 0),
+            ("42".into(), 5),
+        ];
+        bbow.extend(pairs);
+        assert_eq!(5, bbow.match_count("the"));
+        assert_eq!(3, bbow.match_count("cat"));
+        assert_eq!(1, bbow.match_count("dog"));
+        assert!(!bbow.contains("bat"));
+        assert_eq!(11, bbow.count());
+
+        // A bag rebuilt from its own pairs is unchanged.
+        let mut rebuilt = Bbow::new();
+        rebuilt.extend(bbow.clone());
+        assert_eq!(bbow, rebuilt);
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut bbow = Bbow::new().extend_from_text("Noise the noise cat NOISE");
+        let before = bbow.count();
+        assert_eq!(0, bbow.remove("Noise")); // with uppercase
+        assert_eq!(0, bbow.remove("noise!")); // with punctuation
+        assert_eq!(0, bbow.remove("dog")); // absent
+        assert_eq!(before, bbow.count());
+
+        assert_eq!(3, bbow.remove("noise"));
+        assert!(!bbow.contains("noise"));
+        assert_eq!(before - 3, bbow.count());
+        assert_eq!(2, bbow.len());
+        assert_eq!(0, bbow.remove("noise"));
+    }
+
+    #[test]
+    fn test_decrement() {
+        let mut bbow = Bbow::new().extend_from_text("the cat the");
+        assert_eq!(1, bbow.decrement("the"));
+        assert_eq!(1, bbow.match_count("the"));
+        assert_eq!(0, bbow.decrement("the"));
+        assert!(!bbow.contains("the"));
+        assert_eq!(0, bbow.decrement("the"));
+        assert_eq!(vec!["cat"], bbow.words().collect::<Vec<_>>());
+
+        assert_eq!(0, bbow.decrement("Cat"));
+        assert_eq!(0, bbow.decrement("cat!"));
+        assert_eq!(1, bbow.count());
+    }
+
+    #[test]
+    fn test_retain_repeated() {
+        let mut bbow = Bbow::new().extend_from_text("the cat and the hat and the bat");
+        bbow.retain(|_, count| count > 1);
+        assert_eq!(2, bbow.len());
+        assert_eq!(3, bbow.match_count("the"));
+        assert_eq!(2, bbow.match_count("and"));
+        for gone in ["cat", "hat", "bat"] {
+            assert!(!bbow.contains(gone));
+        }
+        assert_eq!(5, bbow.count());
+    }
+
+    #[test]
+    fn test_clear_and_reuse() {
+        let mut bbow = Bbow::new();
+        bbow.add_text("First document, first words.");
+        assert_eq!(2, bbow.match_count("first"));
+
+        bbow.clear();
+        assert!(bbow.is_empty());
+        assert_eq!(0, bbow.len());
+        assert_eq!(0, bbow.count());
+
+        bbow.add_text("Second document.");
+        assert_eq!(0, bbow.match_count("first"));
+        assert_eq!(1, bbow.match_count("second"));
+        assert_eq!(2, bbow.count());
+    }
+
+    #[test]
+    fn test_to_csv() {
+        let bbow = Bbow::new().extend_from_text("The cat and the hat, café 日本語!");
+        let csv = bbow.to_csv();
+        let mut lines = csv.lines();
+        assert_eq!(Some("word,count"), lines.next());
+        let rows: Vec<(&str, usize)> = lines
+            .map(|line| {
+                let (word, count) = line.rsplit_once(',').unwrap();
+                (word, count.parse().unwrap())
+            })
+            .collect();
+        assert_eq!(bbow.len() + 1, csv.lines().count());
+        assert_eq!((&bbow).into_iter().collect::<Vec<_>>(), rows);
+
+        assert_eq!("word,count\n", Bbow::new().to_csv());
+    }
+
+    #[test]
+    fn test_to_csv_escaping() {
+        // Words can't normally contain these characters, so
+        // build the map directly.
+        let mut bbow = Bbow::new();
+        bbow.0.insert(Cow::Borrowed("a,b"), 1);
+        bbow.0.insert(Cow::Borrowed("say \"hi\""), 2);
+        assert_eq!(
+            "word,count\n\"a,b\",1\n\"say \"\"hi\"\"\",2\n",
+            bbow.to_csv()
+        );
+    }
+
+    #[test]
+    fn test_extend_from_reader_matches_text() {
+        let text = "Lets iterate over this text.\nLets iterate\n\nover THAT text, café!";
+        let expected = Bbow::new().extend_from_text(text);
+
+        let mut bbow = Bbow::new();
+        bbow.extend_from_reader(text.as_bytes()).unwrap();
+        assert_eq!(expected.len(), bbow.len());
+        assert_eq!(expected.count(), bbow.count());
+        for (word, count) in &expected {
+            assert_eq!(count, bbow.match_count(word));
+        }
+        // The final line has no trailing newline
+        assert_eq!(1, bbow.match_count("café"));
+        assert!(bbow.0.keys().all(|w| matches!(w, Cow::Owned(_))));
+    }
+
+    #[test]
+    fn test_extend_from_reader_errors() {
+        let mut bbow = Bbow::new();
+        let bytes: &[u8] = b"good words\nbad \xff bytes\n";
+        let err = bbow.extend_from_reader(bytes).unwrap_err();
+        assert_eq!(io::ErrorKind::InvalidData, err.kind());
+        assert_eq!(1, bbow.match_count("good"));
+    }
+
+    /// Path of a scratch file for test `name`, unique to
+    /// this process.
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("bbow-{}-{}", std::process::id(), name))
+    }
+
+    #[test]
+    fn test_from_file() {
+        let path = temp_path("from_file.txt");
+        std::fs::write(&path, "Hello world.\nHello again, world!").unwrap();
+        let bbow = Bbow::from_file(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(3, bbow.len());
+        assert_eq!(2, bbow.match_count("hello"));
+        assert_eq!(2, bbow.match_count("world"));
+        assert_eq!(1, bbow.match_count("again"));
+    }
+
+    #[test]
+    fn test_from_file_errors() {
+        let path = temp_path("from_file_invalid.txt");
+        std::fs::write(&path, b"caf\xe9").unwrap();
+        let err = Bbow::from_file(&path).unwrap_err();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(io::ErrorKind::InvalidData, err.kind());
+
+        let err = Bbow::from_file(temp_path("does_not_exist.txt")).unwrap_err();
+        assert_eq!(io::ErrorKind::NotFound, err.kind());
+    }
+
+    #[cfg(feature = "stopwords")]
+    #[test]
+    fn test_remove_stopwords_en() {
+        let mut bbow = Bbow::new().extend_from_text("The cat sat on the mat, and I saw it.");
+        let before = bbow.count();
+        bbow.remove_stopwords_en();
+        assert!(!bbow.contains("the"));
+        assert!(!bbow.contains("i"));
+        assert_eq!(before - 6, bbow.count());
+        assert_eq!(
+            vec!["cat", "mat", "sat", "saw"],
+            bbow.words().collect::<Vec<_>>()
+        );
+
+        let config = BbowConfig {
+            case_sensitive: true,
+            ..BbowConfig::default()
+        };
+        let mut bbow = Bbow::with_config(config).extend_from_text("The cat AND the Hat");
+        bbow.remove_stopwords_en();
+        assert_eq!(vec!["Hat", "cat"], bbow.words().collect::<Vec<_>>());
+    }
+
+    #[cfg(feature = "stopwords")]
+    #[test]
+    fn test_stopwords_are_sorted_words() {
+        assert!(stopwords::ENGLISH.windows(2).all(|w| w[0] < w[1]));
+        assert!(stopwords::ENGLISH
+            .iter()
+            .all(|w| is_keyword(w, &BbowConfig::default())));
+    }
+
+    #[test]
+    fn test_remove_words() {
+        let mut bbow = Bbow::new().extend_from_text("foo bar foo baz qux Foo");
+        let noise = vec![
+            String::from("FOO"),
+            String::from("baz!"),
+            String::from("absent"),
+        ];
+        bbow.remove_words(&noise);
+        assert!(!bbow.contains("foo"));
+        assert!(!bbow.contains("baz"));
+        assert_eq!(2, bbow.len());
+        assert_eq!(2, bbow.count());
+        assert_eq!(1, bbow.match_count("bar"));
+
+        // Invalid and empty inputs are ignored
+        bbow.remove_words(["", "42", "..."]);
+        bbow.remove_words(Vec::<&str>::new());
+        assert_eq!(2, bbow.count());
+    }
+
+    #[test]
+    fn test_min_len() {
+        let bbow = Bbow::new().extend_from_text_min_len("I said ok, a b c OK!", 2);
+        assert!(!bbow.contains("i"));
+        assert!(!bbow.contains("a"));
+        assert_eq!(2, bbow.match_count("ok"));
+        assert_eq!(1, bbow.match_count("said"));
+        assert_eq!(2, bbow.len());
+
+        // Length counts chars, not bytes: "café" is 4 chars, 5 bytes
+        let bbow = Bbow::new().extend_from_text_min_len("café cafés", 5);
+        assert!(!bbow.contains("café"));
+        assert!(bbow.contains("cafés"));
+
+        // A minimum of 0 or 1 keeps everything
+        let text = "a bb ccc";
+        assert_eq!(3, Bbow::new().extend_from_text_min_len(text, 0).len());
+        assert_eq!(3, Bbow::new().extend_from_text_min_len(text, 1).len());
+    }
+
+    #[test]
+    fn test_separators() {
+        let is_sep = |c| c == '|' || c == ';';
+        let bbow = Bbow::new().extend_from_text_with_separators("a|b;c", is_sep);
+        assert_eq!(vec!["a", "b", "c"], bbow.words().collect::<Vec<_>>());
+
+        // Pieces are trimmed and normalized, empty pieces are
+        // skipped, and whitespace does not separate words.
+        let bbow = Bbow::new().extend_from_text_with_separators(" The |(cat)||;two words;", is_sep);
+        assert_eq!(vec!["cat", "the"], bbow.words().collect::<Vec<_>>());
+
+        let bbow = bbow.extend_from_text_with_separators("cat,the cat", |c| c == ',' || c == ' ');
+        assert_eq!(3, bbow.match_count("cat"));
+        assert_eq!(2, bbow.match_count("the"));
+    }
+
+    #[test]
+    fn test_default_config_unchanged() {
+        let text = "Can't stop this! Stop! TEST test a café";
+        let default = Bbow::new().extend_from_text(text);
+        let configured = Bbow::with_config(BbowConfig::default()).extend_from_text(text);
+        assert_eq!(BbowConfig::default(), *configured.config());
+        assert_eq!(default.len(), configured.len());
+        assert_eq!(default.count(), configured.count());
+        assert_eq!(
+            (&default).into_iter().collect::<Vec<_>>(),
+            (&configured).into_iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_config_case_sensitive() {
+        let config = BbowConfig {
+            case_sensitive: true,
+            ..BbowConfig::default()
+        };
+        let bbow = Bbow::with_config(config).extend_from_text("Test test TEST test.");
+        assert_eq!(3, bbow.len());
+        assert_eq!(2, bbow.match_count("test"));
+        assert_eq!(1, bbow.match_count("Test"));
+        assert_eq!(1, bbow.match_count("TEST"));
+        assert_eq!(0, bbow.match_count("tEST"));
+        assert!(bbow.contains("TEST"));
+        assert_eq!(1, bbow.match_count_normalized("Test!"));
+    }
+
+    #[test]
+    fn test_config_min_len() {
+        let config = BbowConfig {
+            min_len: 3,
+            ..BbowConfig::default()
+        };
+        let mut bbow = Bbow::with_config(config);
+        bbow.add_text("I am a big café owner");
+        bbow.extend(["ox", "Yak"]);
+        assert_eq!(
+            vec!["big", "café", "owner", "yak"],
+            bbow.words().collect::<Vec<_>>()
+        );
+
+        // The larger of the two minimums applies
+        let bbow = Bbow::with_config(config).extend_from_text_min_len("ab abc abcd", 2);
+        assert_eq!(2, bbow.len());
+        let bbow = Bbow::with_config(config).extend_from_text_min_len("ab abc abcd", 4);
+        assert_eq!(1, bbow.len());
+    }
+
+    #[test]
+    fn test_cased_mode_keeps_case() {
+        let text = "Apple apple US us";
+        let cased = Bbow::with_config(BbowConfig {
+            case_sensitive: true,
+            ..BbowConfig::default()
+        })
+        .extend_from_text(text);
+        let folded = Bbow::new().extend_from_text(text);
+
+        assert_eq!(4, cased.len());
+        assert_eq!(2, folded.len());
+        assert_eq!(1, cased.match_count("Apple"));
+        assert_eq!(1, cased.match_count("apple"));
+        assert_eq!(1, cased.match_count("US"));
+        assert_eq!(0, cased.match_count("APPLE"));
+        assert_eq!(2, folded.match_count("apple"));
+        assert_eq!(0, folded.match_count("Apple"));
+
+        let mut cased = cased;
+        assert_eq!(1, cased.remove("US"));
+        assert!(cased.contains("us"));
+        cased.remove_words(["Apple"]);
+        assert!(cased.contains("apple"));
+        assert!(!cased.contains("Apple"));
+    }
+
+    #[test]
+    fn test_cosine_similarity() {
+        let a = Bbow::new().extend_from_text("a b b");
+        let b = Bbow::new().extend_from_text("b c c c");
+        // a = (1, 2, 0), b = (0, 1, 3) over (a, b, c):
+        // dot = 2, |a| = sqrt(5), |b| = sqrt(10)
+        let expected = 2.0 / 50f64.sqrt();
+        assert!((a.cosine_similarity(&b) - expected).abs() < 1e-12);
+        assert!((b.cosine_similarity(&a) - expected).abs() < 1e-12);
+
+        assert!((a.cosine_similarity(&a) - 1.0).abs() < 1e-12);
+        let disjoint = Bbow::new().extend_from_text("x y z");
+        assert_eq!(0.0, a.cosine_similarity(&disjoint));
+        assert_eq!(0.0, a.cosine_similarity(&Bbow::new()));
+        assert_eq!(0.0, Bbow::new().cosine_similarity(&Bbow::new()));
+    }
+
+    #[test]
+    fn test_jaccard_similarity() {
+        let a = Bbow::new().extend_from_text("a b c d");
+        let b = Bbow::new().extend_from_text("c d d e f f f");
+        // shared {c, d}, union {a, b, c, d, e, f}
+        assert_eq!(2.0 / 6.0, a.jaccard_similarity(&b));
+        assert_eq!(2.0 / 6.0, b.jaccard_similarity(&a));
+
+        // Counts are ignored
+        let a2 = Bbow::new().extend_from_text("a a a b c d d");
+        assert_eq!(1.0, a.jaccard_similarity(&a2));
+
+        let disjoint = Bbow::new().extend_from_text("x y");
+        assert_eq!(0.0, a.jaccard_similarity(&disjoint));
+        assert_eq!(0.0, a.jaccard_similarity(&Bbow::new()));
+        assert_eq!(1.0, Bbow::new().jaccard_similarity(&Bbow::new()));
+    }
+
+    #[test]
+    fn test_dice_coefficient() {
+        let a = Bbow::new().extend_from_text("a b c d");
+        let b = Bbow::new().extend_from_text("c d d e e e");
+        // 2 shared words of 4 + 3 unique words.
+        assert_eq!(4.0 / 7.0, a.dice_coefficient(&b));
+        assert_eq!(a.dice_coefficient(&b), b.dice_coefficient(&a));
+
+        let same = Bbow::new().extend_from_text("d c b a a");
+        assert_eq!(1.0, a.dice_coefficient(&same));
+        let disjoint = Bbow::new().extend_from_text("x y");
+        assert_eq!(0.0, a.dice_coefficient(&disjoint));
+        assert_eq!(0.0, a.dice_coefficient(&Bbow::new()));
+        assert_eq!(1.0, Bbow::new().dice_coefficient(&Bbow::new()));
+    }
+
+    #[test]
+    fn test_euclidean_distance() {
+        let a = Bbow::new().extend_from_text("a b b c");
+        let b = Bbow::new().extend_from_text("b c c c d d");
+        // a: 1 - 0, b: 2 - 1, c: 1 - 3, d: 0 - 2
+        let expected = (1.0f64 + 1.0 + 4.0 + 4.0).sqrt();
+        assert_eq!(expected, a.euclidean_distance(&b));
+        assert_eq!(expected, b.euclidean_distance(&a));
+
+        assert_eq!(0.0, a.euclidean_distance(&a.clone()));
+        assert_eq!(6.0f64.sqrt(), a.euclidean_distance(&Bbow::new()));
+        assert_eq!(0.0, Bbow::new().euclidean_distance(&Bbow::new()));
+    }
+
+    #[test]
+    fn test_union() {
+        let a = Bbow::new().extend_from_text("the the cat");
+        let b = Bbow::new().extend_from_text("the hat");
+        let (a_before, b_before) = (a.clone(), b.clone());
+        let all = a.union(&b);
+        assert_eq!(a_before, a);
+        assert_eq!(b_before, b);
+
+        assert_eq!(vec!["cat", "hat", "the"], all.words().collect::<Vec<_>>());
+        assert_eq!(3, all.match_count("the"));
+        assert_eq!(1, all.match_count("hat"));
+        assert_eq!(a.count() + b.count(), all.count());
+        assert_eq!(a.clone() + b.clone(), all);
+        assert_eq!(a, a.union(&Bbow::new()));
+    }
+
+    #[test]
+    fn test_intersection() {
+        let a = Bbow::new().extend_from_text("a a a b");
+        let b = Bbow::new().extend_from_text("a c c c c c");
+        let common = a.intersection(&b);
+        assert_eq!(vec![("a", 1)], (&common).into_iter().collect::<Vec<_>>());
+        assert_eq!(
+            (&common).into_iter().collect::<Vec<_>>(),
+            (&b.intersection(&a)).into_iter().collect::<Vec<_>>()
+        );
+        // Inputs are unchanged
+        assert_eq!(4, a.count());
+        assert_eq!(6, b.count());
+
+        assert!(a.intersection(&Bbow::new()).is_empty());
+        let disjoint = Bbow::new().extend_from_text("x y");
+        assert!(a.intersection(&disjoint).is_empty());
+    }
+
+    #[test]
+    fn test_difference() {
+        let a = Bbow::new().extend_from_text("a a a a a b");
+        let b = Bbow::new().extend_from_text("a a");
+        let rest = a.difference(&b);
+        assert_eq!(
+            vec![("a", 3), ("b", 1)],
+            (&rest).into_iter().collect::<Vec<_>>()
+        );
+
+        // No underflow; words reaching zero and words only
+        // in other are dropped
+        let rest = b.difference(&a);
+        assert!(rest.is_empty());
+        let c = Bbow::new().extend_from_text("b c");
+        assert_eq!(
+            vec![("a", 5)],
+            (&a.difference(&c)).into_iter().collect::<Vec<_>>()
+        );
+        assert_eq!(a.count(), a.difference(&Bbow::new()).count());
+    }
+
+    #[test]
+    fn test_subset_superset() {
+        let small = Bbow::new().extend_from_text("a b b");
+        let large = Bbow::new().extend_from_text("a b b b c");
+        // Proper subset
+        assert!(small.is_subset(&large));
+        assert!(large.is_superset(&small));
+        assert!(!large.is_subset(&small));
+        assert!(!small.is_superset(&large));
+
+        // Improper subset: a bag is a subset of itself
+        assert!(small.is_subset(&small));
+        assert!(small.is_superset(&small));
+
+        // Same words, but not enough occurrences
+        let more = Bbow::new().extend_from_text("a a b");
+        assert!(!more.is_subset(&large));
+
+        // Empty bags
+        let empty = Bbow::new();
+        assert!(empty.is_subset(&small));
+        assert!(empty.is_subset(&empty));
+        assert!(small.is_superset(&empty));
+        assert!(!small.is_subset(&empty));
+    }
+
+    #[test]
+    fn test_words_with_prefix() {
+        let bbow = Bbow::new().extend_from_text("dog cat café car can cab dog");
+        let words: Vec<&str> = bbow.words_with_prefix("ca").map(|(w, _)| w).collect();
+        assert_eq!(vec!["cab", "café", "can", "car", "cat"], words);
+        assert_eq!(
+            vec![("dog", 2)],
+            bbow.words_with_prefix("d").collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![("café", 1)],
+            bbow.words_with_prefix("café").collect::<Vec<_>>()
+        );
+        assert_eq!(bbow.len(), bbow.words_with_prefix("").count());
+        assert_eq!(0, bbow.words_with_prefix("cx").count());
+        assert_eq!(0, bbow.words_with_prefix("zebra").count());
+    }
+
+    #[test]
+    fn test_prefix_count() {
+        let bbow =
+            Bbow::new().extend_from_text("inter internal internal interval intra other intern");
+        let manual = bbow.match_count("inter")
+            + bbow.match_count("intern")
+            + bbow.match_count("internal")
+            + bbow.match_count("interval");
+        assert_eq!(manual, bbow.prefix_count("inter"));
+        assert_eq!(5, bbow.prefix_count("inter"));
+        assert_eq!(6, bbow.prefix_count("int"));
+        assert_eq!(bbow.count(), bbow.prefix_count(""));
+        assert_eq!(0, bbow.prefix_count("zz"));
+    }
+
+    #[test]
+    fn test_levenshtein() {
+        let chars = |s: &str| s.chars().collect::<Vec<_>>();
+        assert_eq!(0, levenshtein(&chars(""), &chars("")));
+        assert_eq!(3, levenshtein(&chars("abc"), &chars("")));
+        assert_eq!(3, levenshtein(&chars("kitten"), &chars("sitting")));
+        assert_eq!(1, levenshtein(&chars("café"), &chars("cafe")));
+    }
+
+    #[test]
+    fn test_nearest() {
+        let bbow = Bbow::new().extend_from_text("hello world help café");
+        assert_eq!(Some("hello"), bbow.nearest("helo", 1));
+        assert_eq!(Some("hello"), bbow.nearest("HELLO", 0));
+        assert_eq!(None, bbow.nearest("helo", 0));
+        assert_eq!(Some("café"), bbow.nearest("cafe", 1));
+        assert_eq!(None, bbow.nearest("zzzzz", 3));
+        assert_eq!(None, Bbow::new().nearest("hello", 10));
+
+        // "held" is one edit from both "hell" and "help": the
+        // alphabetically first wins.
+        let bbow = Bbow::new().extend_from_text("help hell");
+        assert_eq!(Some("hell"), bbow.nearest("held", 1));
+    }
+
+    #[cfg(feature = "unicode-normalization")]
+    #[test]
+    fn test_nearest_composes_query() {
+        let bbow = Bbow::new().extend_from_text("café cafes");
+        assert_eq!(Some("café"), bbow.nearest("cafe\u{301}", 0));
+        assert_eq!(Some("café"), bbow.nearest("CAFE\u{301}", 0));
+    }
+
+    #[test]
+    fn test_bigrams() {
+        let pairs = bigrams("the cat sat");
+        let pairs: Vec<(&str, &str, usize)> = pairs
+            .iter()
+            .map(|((first, second), &count)| (first.as_ref(), second.as_ref(), count))
+            .collect();
+        assert_eq!(vec![("cat", "sat", 1), ("the", "cat", 1)], pairs);
+
+        // Repeats are counted, case is folded and invalid
+        // tokens are skipped
+        let pairs = bigrams("The cat -- the CAT, 42 the cat.");
+        assert_eq!(Some(&3), pairs.get(&("the".into(), "cat".into())));
+        assert_eq!(Some(&2), pairs.get(&("cat".into(), "the".into())));
+        assert_eq!(2, pairs.len());
+
+        // Bigrams cross sentence boundaries
+        let pairs = bigrams("Hi. Bye.");
+        assert_eq!(Some(&1), pairs.get(&("hi".into(), "bye".into())));
+
+        assert!(bigrams("").is_empty());
+        assert!(bigrams("lonely").is_empty());
+    }
+
+    #[test]
+    fn test_sentences() {
+        let collect = |text| -> Vec<Vec<Cow<str>>> {
+            sentences(text).map(|sentence| sentence.collect()).collect()
+        };
+        assert_eq!(
+            vec![vec!["hi", "there"], vec!["bye", "now"]],
+            collect("Hi there. Bye now.")
+        );
+
+        // The last sentence needs no punctuation, and
+        // sentences without words are skipped.
+        assert_eq!(
+            vec![vec!["wait"], vec!["what"], vec!["no", "way"]],
+            collect("Wait... ... what?! (no) way")
+        );
+        assert_eq!(
+            vec![vec!["he", "said", "hi"], vec!["pi", "is"]],
+            collect("He said \"hi.\" Pi is 3.14.")
+        );
+        assert_eq!(vec![vec!["one"]], collect("\u{FEFF}One."));
+        assert!(collect("").is_empty());
+        assert!(collect(" ?! ").is_empty());
+    }
+
+    #[test]
+    fn test_lexical_diversity() {
+        // 4 unique words in 8: the x3, cat x2, sat x2, mat
+        let bbow = Bbow::new().extend_from_text("the cat sat. The mat, the cat sat.");
+        assert_eq!(0.5, bbow.type_token_ratio());
+        assert_eq!(1, bbow.hapax_count());
+
+        let empty = Bbow::new();
+        assert_eq!(0.0, empty.type_token_ratio());
+        assert_eq!(0, empty.hapax_count());
+
+        let unique = Bbow::new().extend_from_text("every word is new");
+        assert_eq!(1.0, unique.type_token_ratio());
+        assert_eq!(4, unique.hapax_count());
+    }
+
+    #[test]
+    fn test_entropy() {
+        let uniform = Bbow::new().extend_from_text("a b c d a b c d");
+        assert_eq!(2.0, uniform.entropy());
+
+        // p = (1/2, 1/4, 1/4): 1/2 * 1 + 2 * (1/4 * 2) = 1.5
+        let skewed = Bbow::new().extend_from_text("a a b c");
+        assert_eq!(1.5, skewed.entropy());
+
+        let single = Bbow::new().extend_from_text("same same same");
+        assert_eq!(0.0, single.entropy());
+        assert_eq!(0.0, Bbow::new().entropy());
+    }
+
+    #[test]
+    fn test_length_histogram() {
+        let bbow = Bbow::new().extend_from_text("café cafe cat cat dog go 日本語");
+        let expected: BTreeMap<usize, usize> = [(2, 1), (3, 3), (4, 2)].into_iter().collect();
+        assert_eq!(expected, bbow.length_histogram());
+        let expected: BTreeMap<usize, usize> = [(2, 1), (3, 4), (4, 2)].into_iter().collect();
+        assert_eq!(expected, bbow.length_histogram_weighted());
+
+        assert!(Bbow::new().length_histogram().is_empty());
+        assert_eq!(bbow.len(), bbow.length_histogram().values().sum::<usize>());
+        assert_eq!(
+            bbow.count(),
+            bbow.length_histogram_weighted().values().sum::<usize>()
+        );
+    }
+
+    #[test]
+    fn test_probability() {
+        let bbow = Bbow::new().extend_from_text("one two two three three three");
+        assert_eq!(0.5, bbow.probability("three"));
+        let total: f64 = bbow.words().map(|w| bbow.probability(w)).sum();
+        assert!((total - 1.0).abs() < 1e-12);
+
+        assert_eq!(0.0, bbow.probability("four"));
+        assert_eq!(0.0, bbow.probability("Three"));
+        assert_eq!(0.0, Bbow::new().probability("one"));
+    }
+
+    #[test]
+    fn test_count_ratio() {
+        let bbow = Bbow::new().extend_from_text("the the the cat cat hat");
+        // Both present
+        assert_eq!(Some(1.5), bbow.count_ratio("the", "cat"));
+        assert_eq!(Some(0.5), bbow.count_ratio("hat", "cat"));
+        assert_eq!(Some(1.0), bbow.count_ratio("cat", "cat"));
+        // One absent
+        assert_eq!(Some(0.0), bbow.count_ratio("dog", "cat"));
+        assert_eq!(None, bbow.count_ratio("cat", "dog"));
+        // Invalid keywords
+        assert_eq!(None, bbow.count_ratio("Cat", "the"));
+        assert_eq!(None, bbow.count_ratio("the", "cat!"));
+        assert_eq!(None, bbow.count_ratio("42", "the"));
+    }
+
+    #[test]
+    fn test_kl_divergence() {
+        let p = Bbow::new().extend_from_text("a b");
+        let q = Bbow::new().extend_from_text("a a a b");
+        // Unsmoothed, by hand:
+        // D(p || q) = 1/2 log2((1/2) / (3/4)) + 1/2 log2((1/2) / (1/4))
+        // D(q || p) = 3/4 log2((3/4) / (1/2)) + 1/4 log2((1/4) / (1/2))
+        let pq = 0.5 * (2.0f64 / 3.0).log2() + 0.5;
+        let qp = 0.75 * 1.5f64.log2() - 0.25;
+        assert!((p.kl_divergence_smoothed(&q, 0.0) - pq).abs() < 1e-12);
+        assert!((q.kl_divergence_smoothed(&p, 0.0) - qp).abs() < 1e-12);
+        assert!(pq != qp);
+
+        // Add-one smoothing: q(a) = 4/6, q(b) = 2/6
+        let smoothed = 0.5 * (0.75f64).log2() + 0.5 * 1.5f64.log2();
+        assert!((p.kl_divergence(&q) - smoothed).abs() < 1e-12);
+
+        // Missing words are finite only with smoothing
+        let r = Bbow::new().extend_from_text("a");
+        assert_eq!(f64::INFINITY, p.kl_divergence_smoothed(&r, 0.0));
+        assert!(p.kl_divergence(&r).is_finite());
+        assert_eq!(f64::INFINITY, p.kl_divergence_smoothed(&Bbow::new(), 0.0));
+        assert!(p.kl_divergence(&Bbow::new()).is_finite());
+
+        assert_eq!(0.0, p.kl_divergence(&p));
+        assert_eq!(0.0, Bbow::new().kl_divergence(&p));
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn test_sample_distribution() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let bbow = Bbow::new().extend_from_text("a b b c c c c c c c");
+        let mut rng = StdRng::seed_from_u64(42);
+        let draws = 100_000;
+        let mut seen = BTreeMap::new();
+        for _ in 0..draws {
+            *seen.entry(bbow.sample(&mut rng).unwrap()).or_insert(0) += 1;
+        }
+        for word in bbow.words() {
+            let expected = bbow.probability(word);
+            let observed = seen[word] as f64 / draws as f64;
+            assert!((expected - observed).abs() < 0.01, "{word}: {observed}");
+        }
+
+        assert_eq!(None, Bbow::new().sample(&mut rng));
     }
 
+    #[cfg(feature = "rayon")]
     #[test]
-    fn test_large_iterative_input() {
-        let mut bbow = Bbow::new().extend_from_text("Lets iterate over this text.");
+    fn test_par_extend_matches_sequential() {
+        // Several megabytes, with multi-byte characters and
+        // assorted whitespace near chunk boundaries.
+        let mut text = String::new();
+        for i in 0..200_000 {
+            text.push_str(match i % 5 {
+                0 => "Lets iterate ",
+                1 => "over\tthis ",
+                2 => "café,\n",
+                3 => "日本語\u{3000}",
+                _ => "b-banana THAT text. ",
+            });
+        }
+        let sequential = Bbow::new().extend_from_text(&text);
+        let parallel = Bbow::new().par_extend_from_text(&text);
+        assert_eq!(sequential.count(), parallel.count());
+        assert_eq!(
+            (&sequential).into_iter().collect::<Vec<_>>(),
+            (&parallel).into_iter().collect::<Vec<_>>()
+        );
 
-        // Verify initial count
-        assert_eq!(5, bbow.count());
+        // Adds to existing counts, and handles tiny inputs
+        let bbow = Bbow::new()
+            .extend_from_text("one")
+            .par_extend_from_text("One two")
+            .par_extend_from_text("");
+        assert_eq!(2, bbow.match_count("one"));
+        assert_eq!(3, bbow.count());
+    }
 
-        //
-        for i in 0..100 {
-            let text = match i % 2 {
-                0 => "Lets iterate over this text.",
-                _ => "Lets iterate over that text.",
-            };
-            bbow = bbow.extend_from_text(text);
+    #[test]
+    fn test_display() {
+        let text = "z y y x x x w w w w v v v v v u u u u u u t t t t t t t \
+                    s s s s s s s s r r r r r r r r r q q q q q q q q q q p";
+        let bbow = Bbow::new().extend_from_text(text);
+        let shown = bbow.to_string();
+        assert!(shown.starts_with("56 words, 11 unique: q (10), r (9), s (8)"));
+        // Only the top ten are shown by default
+        assert!(shown.ends_with("y (2), p (1)"));
+        assert!(!shown.contains("z (1)"));
+        assert_eq!("56 words, 11 unique: q (10), r (9)", format!("{bbow:.2}"));
+        assert_eq!("56 words, 11 unique", format!("{bbow:.0}"));
+        assert_eq!("0 words, 0 unique", Bbow::new().to_string());
+
+        let bbow = Bbow::new().extend_from_text("the cat");
+        assert_eq!("2 words, 2 unique: cat (1), the (1)", format!("{bbow:5}"));
+        assert_eq!("   2 words, 2 unique", format!("{bbow:>20.0}"));
+        assert_eq!("2 words, 2 unique   ", format!("{bbow:20.0}"));
+        assert_eq!("*2 words, 2 unique**", format!("{bbow:*^20.0}"));
+        assert_eq!("2 words, 2 unique: cat (1)---", format!("{bbow:-<29.1}"));
+    }
+
+    #[test]
+    fn test_equality() {
+        let a = Bbow::new().extend_from_text("one two two three three three");
+        let b = Bbow::new()
+            .extend_from_text("Three two")
+            .extend_from_text("THREE, one; three two!");
+        assert_eq!(a, b);
+        assert_eq!(b, a);
+
+        let owned = b.clone().into_owned();
+        assert_eq!(a, owned);
+
+        // Same words, different counts
+        let c = Bbow::new().extend_from_text("one two three");
+        assert_ne!(a, c);
+        assert_ne!(a, Bbow::new());
+        assert_eq!(Bbow::new(), Bbow::new());
+
+        // Configuration is not compared
+        let cased = Bbow::with_config(BbowConfig {
+            case_sensitive: true,
+            ..BbowConfig::default()
+        })
+        .extend_from_text("one two three");
+        assert_eq!(c, cased);
+    }
+
+    #[test]
+    fn test_hash_set_dedup() {
+        use std::collections::HashSet;
+
+        let a = Bbow::new().extend_from_text("the cat and the hat");
+        let b = Bbow::new()
+            .extend_from_text("Hat, the and THE cat.")
+            .into_owned();
+        let c = Bbow::new().extend_from_text("the cat");
+        let mut set = HashSet::new();
+        set.insert(a.clone());
+        set.insert(b);
+        assert_eq!(1, set.len());
+        assert!(set.contains(&a));
+        set.insert(c);
+        assert_eq!(2, set.len());
+    }
+
+    #[test]
+    fn test_keep_contractions() {
+        let config = BbowConfig {
+            keep_contractions: true,
+            ..BbowConfig::default()
+        };
+        let bbow = Bbow::with_config(config).extend_from_text("don't don't can't");
+        assert_eq!(2, bbow.match_count("don't"));
+        assert_eq!(1, bbow.match_count("can't"));
+        assert_eq!(2, bbow.len());
+
+        // Curly apostrophes are stored as straight ones, and
+        // leading, trailing and doubled apostrophes don't count
+        let bbow = Bbow::with_config(config)
+            .extend_from_text("Don’t 'quoted' rock'n'roll dogs' o''clock ' it's");
+        assert_eq!(1, bbow.match_count("don't"));
+        assert_eq!(1, bbow.match_count("quoted"));
+        assert_eq!(1, bbow.match_count("rock'n'roll"));
+        assert_eq!(1, bbow.match_count("dogs"));
+        assert_eq!(1, bbow.match_count("it's"));
+        assert_eq!(0, bbow.match_count("o''clock"));
+        assert_eq!(5, bbow.len());
+        assert_eq!(1, bbow.match_count_normalized("DON’T"));
+
+        // Contractions are ignored by default
+        let bbow = Bbow::new().extend_from_text("don't can't");
+        assert!(bbow.is_empty());
+    }
+
+    #[test]
+    fn test_keep_hyphens() {
+        let config = BbowConfig {
+            keep_hyphens: true,
+            ..BbowConfig::default()
+        };
+        let bbow = Bbow::with_config(config).extend_from_text("well-being Well-Being");
+        assert_eq!(2, bbow.match_count("well-being"));
+        assert_eq!(1, bbow.len());
+
+        let bbow = Bbow::with_config(config)
+            .extend_from_text("Mother-in-law - -- -state- state--art b-banana 3-d");
+        assert_eq!(1, bbow.match_count("mother-in-law"));
+        assert_eq!(1, bbow.match_count("state"));
+        assert_eq!(1, bbow.match_count("b-banana"));
+        assert_eq!(0, bbow.match_count("state--art"));
+        assert_eq!(4, bbow.len());
+
+        // Hyphens count towards the minimum length
+        let bbow = Bbow::with_config(BbowConfig {
+            min_len: 3,
+            ..config
+        })
+        .extend_from_text("a-b ab");
+        assert_eq!(vec!["a-b"], bbow.words().collect::<Vec<_>>());
+
+        // Hyphenated words are ignored by default
+        let bbow = Bbow::new().extend_from_text("well-being");
+        assert!(bbow.is_empty());
+    }
+
+    #[cfg(feature = "case-fold")]
+    #[test]
+    fn test_case_fold() {
+        let bbow = Bbow::new().extend_from_text("Straße STRASSE strasse STRAẞE");
+        assert_eq!(1, bbow.len());
+        assert_eq!(4, bbow.match_count("strasse"));
+        // Keywords must be folded too
+        assert_eq!(0, bbow.match_count("straße"));
+        assert_eq!(4, bbow.match_count_normalized("Straße"));
+
+        // Final sigma, written correctly or not
+        let bbow = Bbow::new().extend_from_text("ΣΟΦΟΣ σοφος σοφοσ");
+        assert_eq!(vec![("σοφοσ", 3)], (&bbow).into_iter().collect::<Vec<_>>());
+
+        let bbow = Bbow::new().extend_from_text("ﬁle file");
+        assert_eq!(2, bbow.match_count("file"));
+    }
+
+    #[cfg(not(feature = "case-fold"))]
+    #[test]
+    fn test_lowercase_without_case_fold() {
+        let bbow = Bbow::new().extend_from_text("Straße STRASSE ΣΟΦΟΣ σοφος");
+        assert_eq!(1, bbow.match_count("straße"));
+        assert_eq!(1, bbow.match_count("strasse"));
+        // str::to_lowercase handles final sigma in context
+        assert_eq!(2, bbow.match_count("σοφος"));
+    }
+
+    #[test]
+    fn test_locale_independent_lowercase() {
+        let bbow = Bbow::new().extend_from_text("İstanbul ISTANBUL istanbul Istanbul ıstanbul");
+        assert_eq!(
+            vec![("istanbul", 4), ("ıstanbul", 1)],
+            (&bbow).into_iter().collect::<Vec<_>>()
+        );
+        assert_eq!(4, bbow.match_count("istanbul"));
+        assert_eq!(0, bbow.match_count("İstanbul"));
+        assert_eq!(4, bbow.match_count_normalized("İSTANBUL"));
+        assert_eq!(1, bbow.match_count_normalized("ıstanbul"));
+        // Every stored word can be matched as a keyword
+        for (word, count) in &bbow {
+            assert_eq!(count, bbow.match_count(word));
         }
+    }
 
-        // Unique count should be: 5 + 1 = 6
-        assert_eq!(6, bbow.len());
-        // Total bag of words should be: 5 + (5 * 50) + (5 * 50) = 505
-        assert_eq!(505, bbow.count());
+    #[test]
+    fn test_concordance() {
+        let text = "The cat sat on the mat. A category of (cats) and one more CAT";
+        assert_eq!(vec!["The cat sat", "ore CAT"], concordance(text, "cat", 4));
+        assert_eq!(vec!["cat", "CAT"], concordance(text, "Cat!", 0));
+        // Windows clamp to the text and count characters
+        assert_eq!(vec![text, text], concordance(text, "cat", 100));
+        let text = "café au lait, café noir";
+        assert_eq!(vec!["café au", "t, café no"], concordance(text, "café", 3));
 
-        // "This" count should be 51
-        assert_eq!(51, bbow.match_count("this"));
-        // "That" count should be 50
-        assert_eq!(50, bbow.match_count("that"));
-        // "iterate" count should be 101
-        assert_eq!(101, bbow.match_count("iterate"));
-        //
+        assert!(concordance(text, "tea", 3).is_empty());
+        assert!(concordance(text, "!!", 3).is_empty());
     }
 
     #[test]
-    fn test_diff_unicode_types() {
-        let mut bbow = Bbow::new().extend_from_text("café café café cafe!");
+    fn test_tokenize_with_spans() {
+        let text = "  \"Hello,\" said   the café-owner -- 42 times... Hello!";
+        let tokens = tokenize_with_spans(text);
+        let words: Vec<&str> = tokens.iter().map(|(word, _)| word.as_ref()).collect();
+        assert_eq!(vec!["hello", "said", "the", "times", "hello"], words);
+        for (word, span) in &tokens {
+            assert_eq!(*word, text[span.clone()].to_lowercase());
+        }
+        assert_eq!("Hello", &text[tokens[0].1.clone()]);
+        assert_eq!(3..8, tokens[0].1);
+
+        let text = "日本語 (café)";
+        let tokens = tokenize_with_spans(text);
+        assert_eq!("café", &text[tokens[1].1.clone()]);
+        assert!(tokenize_with_spans("").is_empty());
+    }
+
+    #[cfg(feature = "unicode-normalization")]
+    #[test]
+    fn test_tokenize_with_spans_combining_marks() {
+        // "café" in decomposed form, with a trailing combining
+        // acute accent.
+        let text = "cafe\u{301} x (nai\u{308}ve)";
+        let tokens = tokenize_with_spans(text);
+        assert_eq!("café", tokens[0].0);
+        assert_eq!(0..6, tokens[0].1);
+        assert_eq!("cafe\u{301}", &text[tokens[0].1.clone()]);
+        assert_eq!("naïve", tokens[2].0);
+        assert_eq!("nai\u{308}ve", &text[tokens[2].1.clone()]);
+    }
+
+    #[test]
+    fn test_get() {
+        let bbow = Bbow::new().extend_from_text("Zebra zebra! ZEBRA? lion");
+        assert_eq!(Some(3), bbow.get("zebra"));
+        assert_eq!(Some(1), bbow.get("lion"));
+        assert_eq!(None, bbow.get("tiger"));
+        assert_eq!(None, bbow.get("Zebra"));
+        assert_eq!(None, bbow.get("zebra!"));
+        assert_eq!(None, bbow.get(""));
+    }
+
+    #[test]
+    fn test_insert() {
+        let mut bbow = Bbow::new().extend_from_text("the dog");
+        assert_eq!(None, bbow.insert("cat", 4));
+        assert_eq!(Some(4), bbow.insert("CAT", 2));
+        assert_eq!(2, bbow.match_count("cat"));
+        assert_eq!(Some(1), bbow.insert("the", 10));
+        assert_eq!(13, bbow.count());
 
+        assert_eq!(Some(1), bbow.insert("dog", 0));
+        assert!(!bbow.contains("dog"));
+        assert_eq!(None, bbow.insert("dog", 0));
+        assert!(!bbow.contains("dog"));
+
+        assert_eq!(None, bbow.insert("x-ray", 7));
+        assert_eq!(None, bbow.insert("", 7));
         assert_eq!(2, bbow.len());
-        assert_eq!(4, bbow.count());
-        assert_eq!(3, bbow.match_count("café"));
-        assert_eq!(1, bbow.match_count("cafe"));
+        assert_eq!(12, bbow.count());
+    }
 
-        // Japanese characters
-        // Source: google translate
-        bbow = bbow.extend_from_text("日本語");
+    #[test]
+    fn test_scale() {
+        let bbow = Bbow::new().extend_from_text("one two two three three three");
+        let mut scaled = bbow.clone();
+        scaled.scale(3);
+        assert_eq!(3, scaled.match_count("one"));
+        assert_eq!(6, scaled.match_count("two"));
+        assert_eq!(9, scaled.match_count("three"));
+        assert_eq!(bbow.count() * 3, scaled.count());
+        assert_eq!(bbow.len(), scaled.len());
+
+        assert_eq!(scaled, bbow.clone() * 3);
+        let mut emptied = bbow;
+        emptied.scale(0);
+        assert!(emptied.is_empty());
+    }
+
+    #[test]
+    fn test_try_add_text() {
+        let mut bbow = Bbow::new();
+        assert_eq!(Ok(()), bbow.try_add_text("The cat saw the dog."));
+        assert_eq!(2, bbow.match_count("the"));
+
+        bbow.insert("dog", usize::MAX - 1);
+        assert_eq!(Ok(()), bbow.try_add_text("a dog"));
+        assert_eq!(usize::MAX, bbow.match_count("dog"));
+
+        let err = bbow.try_add_text("one more Dog! please").unwrap_err();
+        assert_eq!("dog", err.word);
+        assert_eq!("count of word \"dog\" overflowed", err.to_string());
+        assert_eq!(usize::MAX, bbow.match_count("dog"));
+        assert_eq!(1, bbow.match_count("more"));
+        assert!(!bbow.contains("please"));
+    }
+
+    #[test]
+    fn test_drain() {
+        let config = BbowConfig {
+            min_len: 2,
+            ..BbowConfig::default()
+        };
+        let mut bbow = Bbow::with_config(config).extend_from_text("to be or not to be");
+        let drained: Vec<(Cow<str>, usize)> = bbow.drain().collect();
+        let drained: Vec<(&str, usize)> = drained.iter().map(|(w, c)| (w.as_ref(), *c)).collect();
+        assert_eq!(vec![("be", 2), ("not", 1), ("or", 1), ("to", 2)], drained);
+        assert!(bbow.is_empty());
+        assert_eq!(0, bbow.count());
+
+        // The configuration is kept after draining.
+        bbow.add_text("a bee");
+        assert_eq!(1, bbow.len());
+
+        drop(bbow.drain());
+        assert!(bbow.is_empty());
+    }
+
+    #[test]
+    fn test_allow_digits() {
+        let config = BbowConfig {
+            allow_digits: true,
+            ..BbowConfig::default()
+        };
+        let bbow = Bbow::with_config(config).extend_from_text("covid19 covid19 3d");
+        assert_eq!(2, bbow.match_count("covid19"));
+        assert_eq!(1, bbow.match_count("3d"));
+        assert_eq!(2, bbow.len());
+
+        let bbow = Bbow::with_config(config).extend_from_text("(COVID19), in 2024! 3.14 x-1");
+        assert_eq!(1, bbow.match_count("covid19"));
+        assert_eq!(1, bbow.match_count("2024"));
+        assert!(!bbow.contains("3.14"));
         assert_eq!(3, bbow.len());
-        assert_eq!(5, bbow.count());
-        assert_eq!(1, bbow.match_count("日本語"));
 
-        // Arabic characters
-        // Source: google translate
-        bbow = bbow.extend_from_text("Hello in arabic: مرحبًا");
-        assert_eq!(7, bbow.len());
+        let config = BbowConfig {
+            keep_hyphens: true,
+            ..config
+        };
+        let bbow = Bbow::with_config(config).extend_from_text("b-52 x-1");
+        assert_eq!(1, bbow.match_count("b-52"));
+        assert_eq!(1, bbow.match_count("x-1"));
+
+        // By default digits are trimmed like punctuation
+        let bbow = Bbow::new().extend_from_text("covid19 3d 2024");
+        assert_eq!(vec!["covid", "d"], bbow.words().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_mode() {
+        assert_eq!(None, Bbow::new().mode());
+
+        let bbow = Bbow::new().extend_from_text("pear fig apple fig pear kiwi");
+        assert_eq!(Some(("fig", 2)), bbow.mode());
+        assert_eq!(
+            bbow.most_common(1),
+            bbow.mode().into_iter().collect::<Vec<_>>()
+        );
+
+        let bbow = Bbow::new().extend_from_text("zebra zebra zebra ant");
+        assert_eq!(Some(("zebra", 3)), bbow.mode());
+    }
+
+    #[test]
+    fn test_rank_frequency() {
+        assert!(Bbow::new().rank_frequency().is_empty());
+
+        let bbow = Bbow::new().extend_from_text("a b a c b a d e e e e");
+        let ranks = bbow.rank_frequency();
+        assert_eq!(vec![(1, 4), (2, 3), (3, 2), (4, 1), (5, 1)], ranks);
+        for (i, &(rank, _)) in ranks.iter().enumerate() {
+            assert_eq!(i + 1, rank);
+        }
+        assert!(ranks.windows(2).all(|w| w[0].1 >= w[1].1));
+        assert_eq!(bbow.count(), ranks.iter().map(|&(_, c)| c).sum::<usize>());
+    }
+
+    #[test]
+    fn test_words_with_count() {
+        let bbow = Bbow::new().extend_from_text("b a c b d a e e e");
+        assert_eq!(vec!["a", "b"], bbow.words_with_count(2).collect::<Vec<_>>());
+        assert_eq!(vec!["c", "d"], bbow.words_with_count(1).collect::<Vec<_>>());
+        assert_eq!(bbow.hapax_count(), bbow.words_with_count(1).count());
+        assert_eq!(vec!["e"], bbow.words_with_count(3).collect::<Vec<_>>());
+        assert_eq!(0, bbow.words_with_count(0).count());
+        assert_eq!(0, bbow.words_with_count(4).count());
+    }
+
+    #[test]
+    fn test_word_length_statistics() {
+        let bbow = Bbow::new();
+        assert_eq!(0, bbow.total_chars());
+        assert_eq!(0.0, bbow.average_word_length());
+
+        // 3 + 2 * 5 + 3 characters in 4 words
+        let bbow = Bbow::new().extend_from_text("The naïve, naïve dog.");
+        assert_eq!(16, bbow.total_chars());
+        assert_eq!(4.0, bbow.average_word_length());
+
+        let bbow = Bbow::new().extend_from_text("日本 is");
+        assert_eq!(4, bbow.total_chars());
+        assert_eq!(2.0, bbow.average_word_length());
+    }
+
+    #[test]
+    fn test_longest_and_shortest_word() {
+        let bbow = Bbow::new();
+        assert_eq!(None, bbow.longest_word());
+        assert_eq!(None, bbow.shortest_word());
+
+        let bbow = Bbow::new().extend_from_text("word");
+        assert_eq!(Some("word"), bbow.longest_word());
+        assert_eq!(Some("word"), bbow.shortest_word());
+
+        // Ties go to the alphabetically first word
+        let bbow = Bbow::new().extend_from_text("pear fig kiwi ant plum bee");
+        assert_eq!(Some("kiwi"), bbow.longest_word());
+        assert_eq!(Some("ant"), bbow.shortest_word());
+
+        // Length is measured in characters, not bytes
+        let bbow = Bbow::new().extend_from_text("ab 日本語 abcd é");
+        assert_eq!(Some("abcd"), bbow.longest_word());
+        assert_eq!(Some("é"), bbow.shortest_word());
+    }
+
+    #[test]
+    fn test_group_by_first_char() {
+        assert!(Bbow::new().group_by_first_char().is_empty());
+
+        let bbow = Bbow::new().extend_from_text("Café cat 日本語 日 apple Été été ant");
+        let groups = bbow.group_by_first_char();
+        assert_eq!(
+            vec!['a', 'c', 'é', '日'],
+            groups.keys().copied().collect::<Vec<_>>()
+        );
+        assert_eq!(vec!["ant", "apple"], groups[&'a']);
+        assert_eq!(vec!["café", "cat"], groups[&'c']);
+        assert_eq!(vec!["été"], groups[&'é']);
+        assert_eq!(vec!["日", "日本語"], groups[&'日']);
+        assert_eq!(bbow.len(), groups.values().map(Vec::len).sum::<usize>());
+    }
+
+    #[test]
+    fn test_first_char_counts() {
+        assert!(Bbow::new().first_char_counts().is_empty());
+
+        let bbow = Bbow::new().extend_from_text("Café cat 日本語 日 apple Été été ant Ωmega ωmega");
+        let counts = bbow.first_char_counts();
+        assert_eq!(
+            vec![('a', 2), ('c', 2), ('é', 1), ('ω', 1), ('日', 2)],
+            counts.into_iter().collect::<Vec<_>>()
+        );
+        let groups = bbow.group_by_first_char();
+        for (first, count) in bbow.first_char_counts() {
+            assert_eq!(groups[&first].len(), count);
+        }
+    }
+
+    #[test]
+    fn test_words_rev() {
+        let bbow = Bbow::new().extend_from_text("the quick brown fox jumps over the lazy dog");
+        let mut forward: Vec<&str> = bbow.words().collect();
+        let backward: Vec<&str> = bbow.words_rev().collect();
+        assert_eq!(bbow.len(), backward.len());
+        forward.reverse();
+        assert_eq!(forward, backward);
+        assert_eq!(Some("brown"), bbow.words_rev().next_back());
+        assert_eq!(0, Bbow::new().words_rev().count());
+    }
+
+    #[test]
+    fn test_to_sorted_vec() {
+        assert!(Bbow::new().to_sorted_vec().is_empty());
+
+        let text = String::from("pear fig apple fig pear kiwi pear");
+        let pairs = Bbow::new().extend_from_text(&text).to_sorted_vec();
+        drop(text);
+        let expected = [("pear", 3), ("fig", 2), ("apple", 1), ("kiwi", 1)];
+        assert_eq!(expected.len(), pairs.len());
+        for ((word, count), (expected_word, expected_count)) in pairs.iter().zip(expected) {
+            assert_eq!(expected_word, word);
+            assert_eq!(expected_count, *count);
+        }
+
+        let bbow = Bbow::new().extend_from_text("one two three two three three");
+        let pairs = bbow.to_sorted_vec();
+        assert_eq!(bbow.len(), pairs.len());
+        let borrowed: Vec<(&str, usize)> = pairs.iter().map(|(w, c)| (w.as_str(), *c)).collect();
+        assert_eq!(bbow.most_common(bbow.len()), borrowed);
+    }
+
+    #[test]
+    fn test_to_distribution() {
+        assert!(Bbow::new().to_distribution().is_empty());
+
+        let bbow = Bbow::new().extend_from_text("a b a c b a d a a b");
+        let distribution = bbow.to_distribution();
+        assert_eq!(bbow.len(), distribution.len());
+        let sum: f64 = distribution.values().sum();
+        assert!((sum - 1.0).abs() < 1e-12);
+        assert_eq!(0.5, distribution["a"]);
+        for (word, count) in &bbow {
+            assert_eq!(bbow.probability(word), distribution[word]);
+            let ratio = distribution[word] / distribution["d"];
+            assert!((ratio - count as f64).abs() < 1e-12);
+        }
+    }
+
+    #[cfg(feature = "unicode-normalization")]
+    #[test]
+    fn test_strip_accents() {
+        let config = BbowConfig {
+            strip_accents: true,
+            ..BbowConfig::default()
+        };
+        let bbow = Bbow::with_config(config).extend_from_text("café cafe");
+        assert_eq!(2, bbow.match_count("cafe"));
+        assert_eq!(2, bbow.match_count("café"));
+        assert_eq!(vec!["cafe"], bbow.words().collect::<Vec<_>>());
+
+        // Decomposed input and uppercase accents are stripped too
+        let bbow = Bbow::with_config(config).extend_from_text("Cafe\u{301} ÉLAN naïve Ørsted");
+        assert_eq!(
+            vec!["cafe", "elan", "naive", "ørsted"],
+            bbow.words().collect::<Vec<_>>()
+        );
+
+        // Accents are kept by default
+        let bbow = Bbow::new().extend_from_text("café cafe");
+        assert_eq!(2, bbow.len());
+    }
+
+    #[test]
+    fn test_ascii_only() {
+        let ascii = BbowConfig {
+            ascii_only: true,
+            ..BbowConfig::default()
+        };
+        let text = "It ain't over until it's OVER, (really) -- b-banana 3d x2 \"Done.\"";
+        for config in [
+            BbowConfig::default(),
+            BbowConfig {
+                case_sensitive: true,
+                min_len: 3,
+                ..BbowConfig::default()
+            },
+            BbowConfig {
+                keep_contractions: true,
+                keep_hyphens: true,
+                allow_digits: true,
+                ..BbowConfig::default()
+            },
+        ] {
+            let unicode = Bbow::with_config(config).extend_from_text(text);
+            let fast = Bbow::with_config(BbowConfig {
+                ascii_only: true,
+                ..config
+            })
+            .extend_from_text(text);
+            assert_eq!(unicode, fast);
+        }
+
+        let bbow = Bbow::with_config(ascii).extend_from_text("“Quoted” café naïve 日本語");
+        assert_eq!(vec!["caf", "quoted"], bbow.words().collect::<Vec<_>>());
+        assert_eq!(1, bbow.match_count("quoted"));
+        assert_eq!(0, bbow.match_count("naïve"));
+
+        let bbow = Bbow::with_config(BbowConfig {
+            keep_contractions: true,
+            ..ascii
+        })
+        .extend_from_text("don't don’t");
+        assert_eq!(1, bbow.match_count("don't"));
+        assert_eq!(1, bbow.len());
+    }
+
+    #[test]
+    fn test_cooccurrences() {
+        let pairs = cooccurrences("The cat saw the (dog), 42 the cat!", 1);
+        let pairs: Vec<((&str, &str), usize)> = pairs
+            .iter()
+            .map(|((a, b), &count)| ((a.as_str(), b.as_str()), count))
+            .collect();
+        assert_eq!(
+            vec![
+                (("cat", "saw"), 1),
+                (("cat", "the"), 2),
+                (("dog", "the"), 2),
+                (("saw", "the"), 1),
+            ],
+            pairs
+        );
+
+        let pairs = cooccurrences("a b a", 2);
+        assert_eq!(Some(&2), pairs.get(&("a".into(), "b".into())));
+        assert_eq!(Some(&1), pairs.get(&("a".into(), "a".into())));
+        assert_eq!(2, pairs.len());
+
+        assert!(cooccurrences("a b c", 0).is_empty());
+        assert!(cooccurrences("lonely", 3).is_empty());
+    }
+
+    #[test]
+    fn test_iter_by_count() {
+        assert_eq!(0, Bbow::new().iter_by_count().count());
+
+        let bbow = Bbow::new().extend_from_text("pear fig apple fig pear kiwi pear");
+        assert_eq!(bbow.mode(), bbow.iter_by_count().next());
+        let pairs: Vec<(&str, usize)> = bbow.iter_by_count().collect();
+        assert!(pairs.windows(2).all(|w| w[0].1 >= w[1].1));
+        assert_eq!(bbow.most_common(bbow.len()), pairs);
+        assert_eq!(
+            vec![("pear", 3), ("fig", 2)],
+            bbow.iter_by_count().take(2).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_extend_from_words() {
+        let owned = String::from("The");
+        let mut bbow = Bbow::new();
+        bbow.extend_from_words(vec![
+            Cow::Borrowed("the"),
+            Cow::Owned(owned),
+            Cow::Owned(String::from("(cat)")),
+            Cow::Borrowed("b-52"),
+            Cow::Borrowed("not a word"),
+        ]);
+        assert_eq!(2, bbow.match_count("the"));
+        assert_eq!(1, bbow.match_count("cat"));
+        assert_eq!(1, bbow.match_count("b"));
+        assert_eq!(3, bbow.len());
+
+        bbow.extend_from_words(vec![String::from("CAT")]);
+        bbow.extend_from_words(["cat"]);
+        assert_eq!(3, bbow.match_count("cat"));
+    }
+
+    #[test]
+    fn test_memory_usage() {
+        let empty = Bbow::new();
+        assert_eq!(std::mem::size_of::<Bbow>(), empty.memory_usage());
+
+        let text = "alpha beta gamma delta epsilon";
+        let borrowed = Bbow::new().extend_from_text(text);
+        assert!(borrowed.memory_usage() > empty.memory_usage());
+
+        let few = Bbow::new().extend_from_text("Alpha Beta").into_owned();
+        let many = Bbow::new().extend_from_text(text).into_owned();
+        assert!(many.memory_usage() > few.memory_usage());
+        assert!(many.memory_usage() >= borrowed.memory_usage() + text.len() - 4);
+
+        // More occurrences of the same words take no more memory
+        let repeated = Bbow::new().extend_from_text(text).extend_from_text(text);
+        assert_eq!(borrowed.memory_usage(), repeated.memory_usage());
+    }
+
+    #[test]
+    fn test_extend_from_chunks() {
+        let text =
+            "Lets iterate over this text.\nLets iterate  over THAT text, café 日本語!\u{2003}end";
+        let expected = Bbow::new().extend_from_text(text);
+        for size in 1..=text.len() {
+            let mut bbow = Bbow::new();
+            bbow.extend_from_chunks(text.as_bytes().chunks(size))
+                .unwrap();
+            assert_eq!(expected, bbow, "chunk size {size}");
+        }
+
+        // A word split in the middle is counted once
+        let mut bbow = Bbow::new();
+        bbow.extend_from_chunks(["the ca", "t sat", " on the mat"])
+            .unwrap();
+        assert_eq!(1, bbow.match_count("cat"));
+        assert!(!bbow.contains("ca"));
+        assert!(!bbow.contains("t"));
+        assert_eq!(6, bbow.count());
+
+        let mut bbow = Bbow::new();
+        bbow.extend_from_chunks(Vec::<Vec<u8>>::new()).unwrap();
+        assert!(bbow.is_empty());
+
+        let err = bbow
+            .extend_from_chunks([&b"good \xff"[..], b"bad"])
+            .unwrap_err();
+        assert_eq!(io::ErrorKind::InvalidData, err.kind());
+        assert_eq!(1, bbow.match_count("good"));
+
+        // Truncated UTF-8 at the very end is an error too
+        let err = bbow.extend_from_chunks([&b"caf\xc3"[..]]).unwrap_err();
+        assert_eq!(io::ErrorKind::InvalidData, err.kind());
+    }
+
+    #[test]
+    fn test_byte_order_mark() {
+        let text = "\u{FEFF}Hello world";
+        let bbow = Bbow::new().extend_from_text(text);
+        assert_eq!(1, bbow.match_count("hello"));
+        assert_eq!(2, bbow.count());
+
+        let spans = tokenize_with_spans(text);
+        assert_eq!("Hello", &text[spans[0].1.clone()]);
+
+        let mut bbow = Bbow::new();
+        bbow.extend_from_reader(text.as_bytes()).unwrap();
+        assert_eq!(1, bbow.match_count("hello"));
+
+        for size in 1..=4 {
+            let mut bbow = Bbow::new();
+            bbow.extend_from_chunks(text.as_bytes().chunks(size))
+                .unwrap();
+            assert_eq!(1, bbow.match_count("hello"));
+            assert_eq!(2, bbow.count());
+        }
+
+        let config = BbowConfig {
+            keep_contractions: true,
+            case_sensitive: true,
+            ..BbowConfig::default()
+        };
+        let bbow = Bbow::with_config(config).extend_from_text("\u{FEFF}It's");
+        assert_eq!(vec!["It's"], bbow.words().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_unicode_separators() {
+        let bbow = Bbow::new().extend_from_text("hello\u{00A0}world");
+        assert_eq!(vec!["hello", "world"], bbow.words().collect::<Vec<_>>());
+
+        let bbow = Bbow::new().extend_from_text("zero\u{200B}width\u{200B}\u{200B}space");
+        assert_eq!(
+            vec!["space", "width", "zero"],
+            bbow.words().collect::<Vec<_>>()
+        );
+
+        let text = "a\u{2007}b\u{202F}c\u{3000}d\u{2028}e\u{205F}f\u{1680}g\u{85}h";
+        assert_eq!(8, Bbow::new().extend_from_text(text).count());
+
+        // Joiners are not separators
+        let bbow = Bbow::new().extend_from_text("a\u{200D}b");
+        assert!(bbow.is_empty());
+
+        let text = "nbsp\u{00A0}and\u{200B}zwsp";
+        let spans = tokenize_with_spans(text);
+        assert_eq!(3, spans.len());
+        assert_eq!("zwsp", &text[spans[2].1.clone()]);
+        for size in 1..=text.len() {
+            let mut bbow = Bbow::new();
+            bbow.extend_from_chunks(text.as_bytes().chunks(size))
+                .unwrap();
+            assert_eq!(3, bbow.count(), "chunk size {size}");
+        }
+    }
+
+    #[test]
+    fn test_frequencies() {
+        assert!(Bbow::new().frequencies().is_empty());
+
+        let text = String::from("The cat and the hat, café!");
+        let bbow = Bbow::new().extend_from_text(&text);
+        let mut frequencies = bbow.frequencies();
+        assert_eq!(bbow.len(), frequencies.len());
+        for (word, count) in &bbow {
+            assert_eq!(Some(&count), frequencies.get(word));
+        }
+
+        *frequencies.get_mut("the").unwrap() += 10;
+        frequencies.remove("cat");
+        frequencies.insert("dog".to_string(), 1);
+        assert_eq!(2, bbow.match_count("the"));
+        assert!(bbow.contains("cat"));
+        assert!(!bbow.contains("dog"));
+
+        drop(bbow);
+        drop(text);
+        assert_eq!(Some(&1), frequencies.get("café"));
+    }
+
+    #[test]
+    fn test_as_map() {
+        let bbow = Bbow::new().extend_from_text("b A a c c c");
+        let map = bbow.as_map();
+        assert_eq!(bbow.len(), map.len());
+        assert_eq!(
+            vec![("a", 2), ("b", 1), ("c", 3)],
+            map.iter()
+                .map(|(word, &count)| (word.as_ref(), count))
+                .collect::<Vec<_>>()
+        );
+        for (word, &count) in map {
+            assert_eq!(bbow.match_count(word), count);
+        }
+        assert_eq!(bbow.count(), map.values().sum::<usize>());
+        assert!(Bbow::new().as_map().is_empty());
+    }
+
+    #[test]
+    fn test_count_quantile() {
+        assert_eq!(0, Bbow::new().count_quantile(0.5));
+
+        // Counts 1, 1, 2, 5, 9
+        let bbow = Bbow::new().extend_from_text("a b c c d d d d d e e e e e e e e e");
+        assert_eq!(2, bbow.count_quantile(0.5));
+        assert_eq!(1, bbow.count_quantile(0.0));
+        assert_eq!(1, bbow.count_quantile(0.4));
+        assert_eq!(2, bbow.count_quantile(0.41));
+        assert_eq!(5, bbow.count_quantile(0.8));
+        assert_eq!(9, bbow.count_quantile(1.0));
+
+        // Out of range quantiles are clamped
+        assert_eq!(1, bbow.count_quantile(-3.0));
+        assert_eq!(9, bbow.count_quantile(7.0));
+        assert_eq!(1, bbow.count_quantile(f64::NAN));
+    }
+
+    #[test]
+    fn test_prune_below() {
+        let mut bbow = Bbow::new().extend_from_text("a b b c c c d e e e e");
+        assert_eq!(0, bbow.prune_below(0));
+        assert_eq!(0, bbow.prune_below(1));
+        assert_eq!(5, bbow.len());
+
+        assert_eq!(2, bbow.prune_below(2));
+        assert_eq!(vec!["b", "c", "e"], bbow.words().collect::<Vec<_>>());
         assert_eq!(9, bbow.count());
-        assert_eq!(1, bbow.match_count("مرحبًا"));
+
+        assert_eq!(0, bbow.prune_below(2));
+        assert_eq!(3, bbow.prune_below(usize::MAX));
+        assert!(bbow.is_empty());
+    }
+
+    #[test]
+    fn test_count_words_above() {
+        let bbow = Bbow::new().extend_from_text("a b b c c c d e e e e");
+        assert_eq!(5, bbow.count_words_above(0));
+        assert_eq!(5, bbow.count_words_above(1));
+        assert_eq!(3, bbow.count_words_above(2));
+        assert_eq!(2, bbow.count_words_above(3));
+        assert_eq!(1, bbow.count_words_above(4));
+        assert_eq!(0, bbow.count_words_above(5));
+        assert_eq!(0, Bbow::new().count_words_above(0));
+
+        let mut pruned = bbow.clone();
+        pruned.prune_below(3);
+        assert_eq!(bbow.count_words_above(3), pruned.len());
+    }
+
+    #[test]
+    fn test_clamp_counts() {
+        let mut bbow = Bbow::new().extend_from_text("a b b c c c d d d d");
+        bbow.clamp_counts(2);
+        assert!(bbow.iter_by_count().all(|(_, count)| count <= 2));
+        assert_eq!(
+            vec![("b", 2), ("c", 2), ("d", 2), ("a", 1)],
+            bbow.iter_by_count().collect::<Vec<_>>()
+        );
+
+        bbow.clamp_counts(usize::MAX);
+        assert_eq!(7, bbow.count());
+
+        bbow.clamp_counts(0);
+        assert!(bbow.is_empty());
     }
 }
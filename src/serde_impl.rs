@@ -0,0 +1,126 @@
+//! `Serialize` and `Deserialize` support for [`Bbow`],
+//! enabled by the `serde` feature.
+//!
+//! A BBOW is represented as a map from each word to its
+//! count, in sorted word order.
+
+use std::fmt;
+
+use serde::de::{self, Deserialize, Deserializer, MapAccess, Visitor};
+use serde::ser::{Serialize, Serializer};
+
+use crate::{is_lowercase, Bbow, BbowConfig};
+
+impl Serialize for Bbow<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.0.iter().map(|(word, count)| (word.as_ref(), count)))
+    }
+}
+
+/// Deserializing always produces owned words, so the
+/// result may be given any lifetime, including `'static`.
+///
+/// Every word is kept exactly as written, with its count,
+/// so a serialized BBOW deserializes to an equal one
+/// whatever its [`BbowConfig`]. The map does not hold the
+/// configuration, so the default one is used, with
+/// [`case_sensitive`](BbowConfig::case_sensitive),
+/// [`keep_contractions`](BbowConfig::keep_contractions),
+/// [`keep_hyphens`](BbowConfig::keep_hyphens) and
+/// [`allow_digits`](BbowConfig::allow_digits) turned on
+/// when some word needs them. A word that is not in the
+/// form a BBOW with that configuration would store, such
+/// as one with surrounding punctuation, is an error, as is
+/// a repeated word or a count of zero.
+impl<'de> Deserialize<'de> for Bbow<'_> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let words = deserializer.deserialize_map(BbowVisitor)?;
+        let mut config = BbowConfig::default();
+        for (word, _) in &words {
+            config.case_sensitive |= !is_lowercase(word);
+            config.keep_contractions |= word.contains('\'');
+            config.keep_hyphens |= word.contains('-');
+            config.allow_digits |= word.chars().any(char::is_numeric);
+        }
+        Bbow::from_stored_words(config, words).map_err(de::Error::custom)
+    }
+}
+
+struct BbowVisitor;
+
+impl<'de> Visitor<'de> for BbowVisitor {
+    type Value = Vec<(String, usize)>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a map from words to counts")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut access: A) -> Result<Self::Value, A::Error> {
+        let mut words = Vec::with_capacity(access.size_hint().unwrap_or(0));
+        while let Some(entry) = access.next_entry()? {
+            words.push(entry);
+        }
+        Ok(words)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_round_trip() {
+        let bbow = Bbow::new().extend_from_text("The cat and the hat, café 日本語!");
+        let json = serde_json::to_string(&bbow).unwrap();
+        assert_eq!(
+            r#"{"and":1,"café":1,"cat":1,"hat":1,"the":2,"日本語":1}"#,
+            json
+        );
+
+        let decoded: Bbow<'static> = serde_json::from_str(&json).unwrap();
+        assert_eq!(bbow.len(), decoded.len());
+        assert_eq!(bbow.count(), decoded.count());
+        for (word, count) in &bbow {
+            assert_eq!(count, decoded.match_count(word));
+        }
+    }
+
+    #[test]
+    fn test_round_trip_keeps_config_words() {
+        let config = BbowConfig {
+            case_sensitive: true,
+            ..BbowConfig::default()
+        };
+        let bbow = Bbow::with_config(config).extend_from_text("Apple apple APPLE apple");
+        let json = serde_json::to_string(&bbow).unwrap();
+        let decoded: Bbow = serde_json::from_str(&json).unwrap();
+        assert_eq!(bbow, decoded);
+        assert_eq!(2, decoded.match_count("apple"));
+        assert_eq!(1, decoded.match_count("Apple"));
+
+        let config = BbowConfig {
+            keep_contractions: true,
+            keep_hyphens: true,
+            allow_digits: true,
+            ..BbowConfig::default()
+        };
+        let bbow = Bbow::with_config(config).extend_from_text("Don't mother-in-law covid19 don't");
+        let json = serde_json::to_string(&bbow).unwrap();
+        let decoded: Bbow = serde_json::from_str(&json).unwrap();
+        assert_eq!(bbow, decoded);
+        assert_eq!(2, decoded.match_count("don't"));
+        assert_eq!(1, decoded.match_count("covid19"));
+    }
+
+    #[test]
+    fn test_deserialize_errors() {
+        let decoded: Bbow = serde_json::from_str(r#"{"hello":2,"world":1}"#).unwrap();
+        assert_eq!(BbowConfig::default(), *decoded.config());
+        assert_eq!(3, decoded.count());
+
+        assert!(serde_json::from_str::<Bbow>(r#"{"hello!":1}"#).is_err());
+        assert!(serde_json::from_str::<Bbow>(r#"{"two words":1}"#).is_err());
+        assert!(serde_json::from_str::<Bbow>(r#"{"word":0}"#).is_err());
+        assert!(serde_json::from_str::<Bbow>(r#"{"word":1,"word":2}"#).is_err());
+    }
+}
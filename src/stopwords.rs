@@ -0,0 +1,154 @@
+//! A builtin list of common English stopwords, enabled
+//! by the `stopwords` feature.
+//!
+//! The list holds the following 123 words:
+//!
+//! `a`, `about`, `above`, `after`, `again`, `against`,
+//! `all`, `am`, `an`, `and`, `any`, `are`, `as`, `at`,
+//! `be`, `because`, `been`, `before`, `being`, `below`,
+//! `between`, `both`, `but`, `by`, `could`, `did`, `do`,
+//! `does`, `doing`, `down`, `during`, `each`, `few`, `for`,
+//! `from`, `further`, `had`, `has`, `have`, `having`, `he`,
+//! `her`, `here`, `hers`, `herself`, `him`, `himself`,
+//! `his`, `how`, `i`, `if`, `in`, `into`, `is`, `it`,
+//! `its`, `itself`, `me`, `more`, `most`, `my`, `myself`,
+//! `no`, `nor`, `not`, `of`, `off`, `on`, `once`, `only`,
+//! `or`, `other`, `ought`, `our`, `ours`, `ourselves`,
+//! `out`, `over`, `own`, `same`, `she`, `should`, `so`,
+//! `some`, `such`, `than`, `that`, `the`, `their`,
+//! `theirs`, `them`, `themselves`, `then`, `there`,
+//! `these`, `they`, `this`, `those`, `through`, `to`,
+//! `too`, `under`, `until`, `up`, `very`, `was`, `we`,
+//! `were`, `what`, `when`, `where`, `which`, `while`,
+//! `who`, `whom`, `why`, `with`, `would`, `you`, `your`,
+//! `yours`, `yourself`, `yourselves`.
+//!
+//! Contractions such as "don't" are not included, since
+//! they are not words under the default rules.
+
+/// Common English stopwords, in sorted order.
+pub const ENGLISH: &[&str] = &[
+    "a",
+    "about",
+    "above",
+    "after",
+    "again",
+    "against",
+    "all",
+    "am",
+    "an",
+    "and",
+    "any",
+    "are",
+    "as",
+    "at",
+    "be",
+    "because",
+    "been",
+    "before",
+    "being",
+    "below",
+    "between",
+    "both",
+    "but",
+    "by",
+    "could",
+    "did",
+    "do",
+    "does",
+    "doing",
+    "down",
+    "during",
+    "each",
+    "few",
+    "for",
+    "from",
+    "further",
+    "had",
+    "has",
+    "have",
+    "having",
+    "he",
+    "her",
+    "here",
+    "hers",
+    "herself",
+    "him",
+    "himself",
+    "his",
+    "how",
+    "i",
+    "if",
+    "in",
+    "into",
+    "is",
+    "it",
+    "its",
+    "itself",
+    "me",
+    "more",
+    "most",
+    "my",
+    "myself",
+    "no",
+    "nor",
+    "not",
+    "of",
+    "off",
+    "on",
+    "once",
+    "only",
+    "or",
+    "other",
+    "ought",
+    "our",
+    "ours",
+    "ourselves",
+    "out",
+    "over",
+    "own",
+    "same",
+    "she",
+    "should",
+    "so",
+    "some",
+    "such",
+    "than",
+    "that",
+    "the",
+    "their",
+    "theirs",
+    "them",
+    "themselves",
+    "then",
+    "there",
+    "these",
+    "they",
+    "this",
+    "those",
+    "through",
+    "to",
+    "too",
+    "under",
+    "until",
+    "up",
+    "very",
+    "was",
+    "we",
+    "were",
+    "what",
+    "when",
+    "where",
+    "which",
+    "while",
+    "who",
+    "whom",
+    "why",
+    "with",
+    "would",
+    "you",
+    "your",
+    "yours",
+    "yourself",
+    "yourselves",
+];
@@ -0,0 +1,163 @@
+//! Approximate most frequent words of a text stream in
+//! bounded memory.
+//!
+//! A [`Bbow`](crate::Bbow) keeps every unique word it has
+//! seen, which is too much for an unbounded stream when
+//! only the most frequent words matter. [`TopK`] instead
+//! keeps a fixed number of counters, using the
+//! Space-Saving algorithm of Metwally, Agrawal and El
+//! Abbadi.
+
+use std::collections::HashMap;
+
+use crate::{normalize, tokens, BbowConfig};
+
+/// An accumulator estimating the `k` most frequent words of
+/// a stream of text, using memory for only `k` words. The
+/// words follow the same rules as for [`Bbow`](crate::Bbow).
+///
+/// The results are exact while no more than `k` unique
+/// words have been pushed. Beyond that they are
+/// approximate: when a new word arrives and all `k`
+/// counters are in use, the word with the lowest count is
+/// evicted and the new word inherits its count. So if `n`
+/// words have been pushed in total:
+///
+/// * A reported count is never less than the true count,
+///   and exceeds it by at most `n / k`.
+/// * Every word occurring more than `n / k` times is
+///   reported.
+///
+/// # Examples
+///
+/// ```
+/// # use bbow::TopK;
+/// let mut top = TopK::new(2);
+/// top.push_text("The the THE cat, the hat");
+/// let words = top.finish();
+/// assert_eq!(("the".to_string(), 4), words[0]);
+/// // With only 2 counters "hat" replaced "cat", inheriting
+/// // its count.
+/// assert_eq!(("hat".to_string(), 2), words[1]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct TopK {
+    k: usize,
+    counts: HashMap<String, usize>,
+    config: BbowConfig,
+}
+
+impl TopK {
+    /// Make a new accumulator for the `k` most frequent
+    /// words.
+    pub fn new(k: usize) -> Self {
+        Self::with_config(k, BbowConfig::default())
+    }
+
+    /// Make a new accumulator for the `k` most frequent
+    /// words, turning text into words according to
+    /// `config`.
+    pub fn with_config(k: usize, config: BbowConfig) -> Self {
+        TopK {
+            k,
+            counts: HashMap::with_capacity(k),
+            config,
+        }
+    }
+
+    /// Parse the `target` text and count the valid words
+    /// contained in it, as in
+    /// [`Bbow::add_text`](crate::Bbow::add_text).
+    pub fn push_text(&mut self, target: &str) {
+        for token in tokens(target) {
+            if let Some(word) = normalize(token, &self.config) {
+                self.push_word(&word);
+            }
+        }
+    }
+
+    fn push_word(&mut self, word: &str) {
+        if let Some(count) = self.counts.get_mut(word) {
+            *count += 1;
+        } else if self.counts.len() < self.k {
+            self.counts.insert(word.to_owned(), 1);
+        } else if let Some((evicted, min)) = self
+            .counts
+            .iter()
+            .min_by(|(a, a_count), (b, b_count)| a_count.cmp(b_count).then(a.cmp(b)))
+            .map(|(evicted, &min)| (evicted.clone(), min))
+        {
+            // Scanning for the minimum costs `O(k)`, but only
+            // for words that are not already counted.
+            self.counts.remove(&evicted);
+            self.counts.insert(word.to_owned(), min + 1);
+        }
+    }
+
+    /// Report the estimated most frequent words and their
+    /// estimated counts, at most `k` of them, sorted by
+    /// descending count and then alphabetically.
+    pub fn finish(self) -> Vec<(String, usize)> {
+        let mut words: Vec<(String, usize)> = self.counts.into_iter().collect();
+        words.sort_unstable_by(|(a, a_count), (b, b_count)| b_count.cmp(a_count).then(a.cmp(b)));
+        words
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Bbow;
+
+    #[test]
+    fn test_exact_for_few_words() {
+        let text = "The cat and the hat and THE bat.";
+        let mut top = TopK::new(10);
+        top.push_text(text);
+        let expected: Vec<(String, usize)> = Bbow::new()
+            .extend_from_text(text)
+            .most_common(10)
+            .into_iter()
+            .map(|(word, count)| (word.to_string(), count))
+            .collect();
+        assert_eq!(expected, top.finish());
+
+        let mut top = TopK::new(0);
+        top.push_text(text);
+        assert!(top.finish().is_empty());
+    }
+
+    #[test]
+    fn test_heavy_hitters() {
+        // Heavy hitters interleaved with many distinct
+        // filler words.
+        let heavy = [
+            "the", "of", "the", "and", "of", "the", "to", "of", "and", "the",
+        ];
+        let k = 20;
+        let mut top = TopK::new(k);
+        let mut text = String::new();
+        let mut state: u32 = 7;
+        for i in 0..3000 {
+            let mut filler = String::new();
+            for _ in 0..5 {
+                state = state.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+                filler.push(char::from(b'a' + (state >> 16) as u8 % 26));
+            }
+            let line = format!("{} {filler}", heavy[i % heavy.len()]);
+            top.push_text(&line);
+            text.push_str(&line);
+            text.push('\n');
+        }
+        let exact = Bbow::new().extend_from_text(&text);
+        let estimated = top.finish();
+        assert_eq!(k, estimated.len());
+
+        let bound = exact.count() / k;
+        for (i, (word, count)) in exact.most_common(4).into_iter().enumerate() {
+            assert_eq!(word, estimated[i].0);
+            assert!(estimated[i].1 >= count);
+            assert!(estimated[i].1 - count <= bound);
+        }
+    }
+}